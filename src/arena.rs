@@ -0,0 +1,303 @@
+//! Strategies playing each other with nobody watching, over and over,
+//! to see which one wins more and by how much. Every game is on a board dealt
+//! from its own seed, so a run can be repeated exactly. Boards are dealt the
+//! usual way or rolled from a set of dice, to compare dice sets
+
+use std::fmt::{self, Display, Formatter};
+use std::sync::Arc;
+
+use board::Board;
+use dice::Dice;
+use dictionary::Dictionary;
+use game::{Game, Player};
+use multiplayer::Cancellation;
+use rules::Rules;
+use strategy::Strategy;
+
+// how a run of games is set up
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArenaSettings {
+    pub games: usize,
+    // the first game's board is dealt from this seed, the next from seed + 1...
+    pub seed: u64,
+    pub rows: usize,
+    pub cols: usize,
+    pub seconds: u64,
+    pub rules: Rules,
+    // dice to roll every board from, the usual letter mix without them
+    pub dice: Option<Dice>,
+}
+
+impl ArenaSettings {
+    // the board for the game dealt from `seed`
+    pub fn board(&self, seed: u64) -> Board {
+        match self.dice {
+            Some(ref dice) => Board::from_dice(self.rows, self.cols, dice, seed),
+            None => Board::from_seed(self.rows, self.cols, seed),
+        }
+    }
+}
+
+// one strategy's results over the whole run
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tally {
+    pub name: String,
+    // games it had the best score in on its own
+    pub wins: usize,
+    // games it shared the best score in
+    pub ties: usize,
+    // after shared words are cancelled
+    pub total_score: usize,
+}
+
+impl Tally {
+    pub fn average(&self, games: usize) -> f64 {
+        if games == 0 {
+            0.0
+        } else {
+            self.total_score as f64 / games as f64
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArenaResults {
+    pub settings: ArenaSettings,
+    // in the order the strategies were given
+    pub tallies: Vec<Tally>,
+}
+
+// play `settings.games` games between `strategies`, scored the way
+// hot-seat games are: a word found by more than one of them scores nothing
+pub fn run(
+    strategies: &mut [Box<dyn Strategy>],
+    dictionary: Arc<Dictionary>,
+    settings: &ArenaSettings,
+) -> ArenaResults {
+    let mut tallies: Vec<Tally> = strategies
+        .iter()
+        .map(|s| Tally {
+            name: s.name(),
+            wins: 0,
+            ties: 0,
+            total_score: 0,
+        })
+        .collect();
+
+    for n in 0..settings.games {
+        let seed = settings.seed.wrapping_add(n as u64);
+        let board = settings.board(seed);
+        let games: Vec<Game> = strategies
+            .iter_mut()
+            .map(|strategy| play(&mut **strategy, &board, &dictionary, settings))
+            .collect();
+        let scores = Cancellation::new(&games).scores;
+
+        let best = scores.iter().cloned().max().unwrap_or(0);
+        let winners = scores.iter().filter(|&&s| s == best).count();
+        for (tally, &score) in tallies.iter_mut().zip(scores.iter()) {
+            tally.total_score += score;
+            if score == best && winners == 1 {
+                tally.wins += 1;
+            } else if score == best {
+                tally.ties += 1;
+            }
+        }
+    }
+
+    ArenaResults {
+        settings: settings.clone(),
+        tallies,
+    }
+}
+
+// one strategy's round, with its guesses checked in the order it makes them
+fn play(
+    strategy: &mut dyn Strategy,
+    board: &Board,
+    dictionary: &Arc<Dictionary>,
+    settings: &ArenaSettings,
+) -> Game {
+    let player = Player::new(strategy.name());
+    let mut game = Game::new(board.clone(), player, Some(dictionary.clone()));
+    game.rules = settings.rules.clone();
+    let mut guesses = strategy.play(board, settings.seconds);
+    guesses.sort_by_key(|&(at, _)| at);
+    for (at, word) in guesses {
+        if at >= settings.seconds {
+            break;
+        }
+        let _ = game.add_guess_at(word, at);
+    }
+    game
+}
+
+impl Display for ArenaResults {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let settings = &self.settings;
+        writeln!(
+            f,
+            "{} games on {}x{} boards, {} seconds each (seeds {} to {})",
+            settings.games,
+            settings.rows,
+            settings.cols,
+            settings.seconds,
+            settings.seed,
+            settings
+                .seed
+                .wrapping_add(settings.games.saturating_sub(1) as u64)
+        )?;
+        if let Some(ref dice) = settings.dice {
+            writeln!(f, "Boards rolled from the {} dice", dice.name())?;
+        }
+        let width = self.tallies
+            .iter()
+            .map(|t| t.name.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max("Strategy".len());
+        writeln!(
+            f,
+            "\n{:<width$}  {:>6}  {:>6}  {:>8}  {:>9}",
+            "Strategy",
+            "Wins",
+            "Ties",
+            "Win rate",
+            "Avg score",
+            width = width
+        )?;
+        for tally in &self.tallies {
+            let rate = if settings.games == 0 {
+                0.0
+            } else {
+                100.0 * tally.wins as f64 / settings.games as f64
+            };
+            writeln!(
+                f,
+                "{:<width$}  {:>6}  {:>6}  {:>7.1}%  {:>9.2}",
+                tally.name,
+                tally.wins,
+                tally.ties,
+                rate,
+                tally.average(settings.games),
+                width = width
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opponent::{Computer, Difficulty};
+
+    // reads the board's only row as a word, `at` seconds in
+    struct Reader {
+        name: &'static str,
+        at: Option<u64>,
+    }
+
+    impl Strategy for Reader {
+        fn name(&self) -> String {
+            self.name.to_string()
+        }
+
+        fn play(&mut self, board: &Board, _: u64) -> Vec<(u64, String)> {
+            let word: String = board.letters().into_iter().collect();
+            self.at.map(|at| (at, word)).into_iter().collect()
+        }
+    }
+
+    fn reader(name: &'static str, at: Option<u64>) -> Box<dyn Strategy> {
+        Box::new(Reader { name, at })
+    }
+
+    // 1x3 boards, with every one the run deals in the dictionary
+    fn settings(games: usize) -> (ArenaSettings, Arc<Dictionary>) {
+        let settings = ArenaSettings {
+            games,
+            seed: 1,
+            rows: 1,
+            cols: 3,
+            seconds: 60,
+            rules: Rules::default(),
+            dice: None,
+        };
+        let words = (0..games as u64).map(|n| {
+            let board = Board::from_seed(1, 3, 1 + n);
+            board.letters().into_iter().collect::<String>()
+        });
+        (settings, Arc::new(Dictionary::from_words(words)))
+    }
+
+    #[test]
+    fn shared_words_cancel_and_late_words_miss() {
+        let (settings, dictionary) = settings(3);
+        let mut strategies = vec![
+            reader("early", Some(0)),
+            reader("late", Some(60)),
+            reader("echo", Some(10)),
+        ];
+        let results = run(&mut strategies, dictionary, &settings);
+        // early and echo always find the same word and late finds
+        // nothing in time, so nobody scores and all three share every win
+        for tally in &results.tallies {
+            assert_eq!(tally.wins, 0);
+            assert_eq!(tally.ties, 3);
+            assert_eq!(tally.total_score, 0);
+        }
+    }
+
+    #[test]
+    fn the_only_scorer_wins() {
+        let (settings, dictionary) = settings(2);
+        let mut strategies = vec![reader("quiet", None), reader("reader", Some(5))];
+        let results = run(&mut strategies, dictionary, &settings);
+        assert_eq!(results.tallies[1].wins, 2);
+        assert_eq!(results.tallies[1].average(2), 1.0);
+        assert_eq!(results.tallies[0].wins, 0);
+
+        let table = results.to_string();
+        assert!(table.starts_with("2 games on 1x3 boards, 60 seconds each (seeds 1 to 2)\n"));
+        assert!(table.contains("\nStrategy    Wins    Ties  Win rate  Avg score\n"));
+        assert!(table.contains("\nreader         2       0    100.0%       1.00\n"));
+    }
+
+    #[test]
+    fn runs_repeat_with_the_same_seed() {
+        let dictionary = Arc::new(Dictionary::from_words(vec![
+            "at", "ate", "eat", "tea", "tan", "ant", "net", "ten", "sat", "set", "sea", "one",
+            "not", "ton", "toe", "note", "tone", "rat", "tar", "art", "ear", "era", "are",
+        ]));
+        let mut results = vec![];
+        for _ in 0..2 {
+            let mut strategies: Vec<Box<dyn Strategy>> = vec![
+                Box::new(Computer::new(Difficulty::Easy, dictionary.clone(), 3)),
+                Box::new(Computer::new(Difficulty::Hard, dictionary.clone(), 4)),
+            ];
+            let (mut settings, _) = settings(50);
+            settings.rows = 4;
+            settings.cols = 4;
+            results.push(run(&mut strategies, dictionary.clone(), &settings));
+        }
+        assert_eq!(results[0], results[1]);
+        assert_eq!(results[0].tallies[1].name, "Computer (hard)");
+        let total: usize = results[0].tallies.iter().map(|t| t.wins + t.ties).sum();
+        assert!(total >= 50);
+    }
+
+    #[test]
+    fn boards_can_be_rolled_from_dice() {
+        let (mut settings, _) = settings(3);
+        settings.dice = Some(Dice::from_reader("dice.txt", &b"C\nA\nT\n"[..]).unwrap());
+        let rolls = vec!["act", "atc", "cat", "cta", "tac", "tca"];
+        let dictionary = Arc::new(Dictionary::from_words(rolls));
+        let mut strategies = vec![reader("reader", Some(5))];
+        let results = run(&mut strategies, dictionary, &settings);
+        // whatever order the dice land in, the row reads as a word
+        assert_eq!(results.tallies[0].total_score, 3);
+        let table = results.to_string();
+        assert!(table.contains("(seeds 1 to 3)\nBoards rolled from the dice.txt dice\n"));
+    }
+}
@@ -1,29 +1,81 @@
-/// Define Board and Pieces
-/// Boards and games have a 1:1 ratio
-/// Boards are made up of 25 Pieces
+//! Define Board and Pieces
+//! Boards and games have a 1:1 ratio
+//! Boards are a grid of Pieces, any number of rows by any number of columns
 
+use std::error::Error;
 use std::fmt::{self, Formatter, Display};
-use rand::{thread_rng, Rng};
+
+use dice::Dice;
+use rand::{thread_rng, Rng, SeedableRng, XorShiftRng};
 use rand::distributions::{Sample, Range};
 
-pub const BOARD_DIMENSIONS: i32 = 4;
+pub const BOARD_DIMENSIONS: usize = 4;
 
 // representation of the playing board
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Board {
+    rows: usize,
+    cols: usize,
     pieces: Vec<Piece>,
+    // neighbor lists for each piece, indexed the same as `pieces`.
+    // computed once when the board is built
+    neighbors: Vec<Vec<usize>>,
     include_borders: bool,
-    pub dictionary: Option<Vec<String>>,
+    // the seed a random board was generated from, so it can be dealt again
+    seed: Option<u64>,
 }
 
 // 0-indexed row/col Piece on the Board
 #[derive(Debug, Clone, Copy)]
 pub struct Piece {
     letter: char,
-    row: i32,
-    col: i32,
+    row: usize,
+    col: usize,
+    index: usize,
+}
+
+// reasons a board could not be built from a list of letters
+#[derive(Debug, Clone, PartialEq)]
+pub enum BoardError {
+    // a board needs at least one row and one column
+    EmptyDimensions,
+    // number of faces given did not match rows * cols
+    WrongLength { expected: usize, actual: usize },
+    // a face that is not a letter, e.g. "3" or "?"
+    NonAlphabetic(String),
+    // faces like "Qu" are not supported yet
+    MultiLetterFace(String),
+    // a board code that isn't "<rows>x<cols>:<letters>"
+    BadCode(String),
+}
+
+impl Display for BoardError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match *self {
+            BoardError::EmptyDimensions => write!(f, "a board needs at least one row and column"),
+            BoardError::WrongLength { expected, actual } => write!(
+                f,
+                "expected {} letters for this board but got {}",
+                expected,
+                actual
+            ),
+            BoardError::NonAlphabetic(ref face) => write!(f, "\"{}\" is not a letter", face),
+            BoardError::MultiLetterFace(ref face) => write!(
+                f,
+                "\"{}\" has more than one letter, which is not supported",
+                face
+            ),
+            BoardError::BadCode(ref code) => write!(
+                f,
+                "\"{}\" is not a board code like 4x4:ABCDEFGHIJKLMNOP",
+                code
+            ),
+        }
+    }
 }
 
+impl Error for BoardError {}
+
 const VOWELS: [char; 5] = ['A', 'E', 'I', 'O', 'U'];
 const CONSONANTS_UNFRIENDLY: [char; 8] = ['J', 'K', 'Q', 'V', 'X', 'W', 'Y', 'Z'];
 const CONSONANTS_FRIENDLY: [char; 13] = [
@@ -42,19 +94,43 @@ const CONSONANTS_FRIENDLY: [char; 13] = [
     'T',
 ];
 
+impl Default for Board {
+    fn default() -> Board {
+        Board::new()
+    }
+}
+
 impl Board {
-    pub fn new(dictionary: Option<Vec<String>>) -> Board {
+    // CATS across the top and DOG on the third row, the board most tests play on
+    #[cfg(test)]
+    pub fn cats_and_dog() -> Board {
+        let faces: Vec<String> = "CATSXXXXDOGXXXXX".chars().map(|c| c.to_string()).collect();
+        Board::try_from_letters(4, 4, &faces).unwrap()
+    }
+
+    // random 4x4 board
+    pub fn new() -> Board {
+        Board::random(BOARD_DIMENSIONS, BOARD_DIMENSIONS)
+    }
+
+    // random board of any size
+    pub fn random(rows: usize, cols: usize) -> Board {
+        Board::from_seed(rows, cols, thread_rng().gen())
+    }
+
+    // the same seed always deals the same board
+    pub fn from_seed(rows: usize, cols: usize, seed: u64) -> Board {
+        let mut rng = seeded_rng(seed);
         let mut pieces = Vec::new();
 
         // generate letters
         // choose vowels ~50% of the time
         // sprinkle some unfriendly consonants with the friendly consonants
-        let mut rng = thread_rng();
         let mut vowel_range = Range::new(0usize, VOWELS.len());
         let mut consonant_unfriendly_range = Range::new(0usize, CONSONANTS_UNFRIENDLY.len());
         let mut consonant_friendly_range = Range::new(0usize, CONSONANTS_FRIENDLY.len());
         let mut unfriendly_range = Range::new(0usize, 10usize);
-        for i in 0..(BOARD_DIMENSIONS.pow(2)) {
+        for i in 0..(rows * cols) {
             let letter = if rng.gen() {
                 VOWELS[vowel_range.sample(&mut rng)]
             } else if unfriendly_range.sample(&mut rng) < 1 {
@@ -62,95 +138,425 @@ impl Board {
             } else {
                 CONSONANTS_FRIENDLY[consonant_friendly_range.sample(&mut rng)]
             };
-            pieces.push(Piece::new(letter, i));
+            pieces.push(Piece::new(letter, i, cols));
         }
 
         Board {
-            pieces: pieces,
+            rows,
+            cols,
+            pieces,
+            neighbors: adjacency(rows, cols),
             include_borders: false,
-            dictionary,
+            seed: Some(seed),
         }
     }
 
-    // used for testing, never used in actual binary
-    #[allow(dead_code)]
-    pub fn from(letters: &Vec<char>) -> Board {
-        let mut pieces = Vec::new();
-        // ideally would convert letter to uppercase,
-        // but the to_uppercase method is not very user friendly
-        // tried: .map(|&x| x.to_uppercase().collect())
-        for (i, l) in letters.iter().enumerate() {
-            pieces.push(Piece::new(*l, i as i32));
+    // a board rolled from `dice`, each die landing on a random face in a
+    // random place. a board with more tiles than there are dice uses the
+    // set again. the same dice and seed always give the same board, but
+    // the seed alone doesn't, so it isn't kept
+    pub fn from_dice(rows: usize, cols: usize, dice: &Dice, seed: u64) -> Board {
+        let mut rng = seeded_rng(seed);
+        let mut order: Vec<&Vec<char>> = vec![];
+        let mut pieces = Vec::with_capacity(rows * cols);
+        for i in 0..(rows * cols) {
+            if order.is_empty() {
+                order = dice.dice().iter().collect();
+                rng.shuffle(&mut order);
+            }
+            let die = order.pop().expect("a dice set has at least one die");
+            let letter = *rng.choose(die).expect("a die has at least one face");
+            pieces.push(Piece::new(letter, i, cols));
         }
+
         Board {
-            pieces: pieces,
+            rows,
+            cols,
+            pieces,
+            neighbors: adjacency(rows, cols),
             include_borders: false,
-            dictionary: None,
+            seed: None,
+        }
+    }
+
+    // a board written out by `code`
+    pub fn from_code(code: &str) -> Result<Board, BoardError> {
+        let bad = || BoardError::BadCode(code.to_string());
+        let mut parts = code.trim().splitn(2, ':');
+        let size = parts.next().ok_or_else(bad)?;
+        let letters = parts.next().ok_or_else(bad)?;
+        let mut dims = size.splitn(2, 'x').map(|n| n.trim().parse::<usize>());
+        let (rows, cols) = match (dims.next(), dims.next()) {
+            (Some(Ok(rows)), Some(Ok(cols))) => (rows, cols),
+            _ => return Err(bad()),
+        };
+        // a code from someone else can ask for any size
+        rows.checked_mul(cols).ok_or_else(bad)?;
+        let faces: Vec<String> = letters.chars().map(|c| c.to_string()).collect();
+        Board::try_from_letters(rows, cols, &faces)
+    }
+
+    // a board written out by `code` that was dealt from `seed`. it keeps the
+    // seed, as long as the seed really deals that board
+    pub fn from_code_and_seed(code: &str, seed: Option<u64>) -> Result<Board, BoardError> {
+        let board = Board::from_code(code)?;
+        if let Some(seed) = seed {
+            let seeded = Board::from_seed(board.rows(), board.cols(), seed);
+            if seeded.code() == board.code() {
+                return Ok(seeded);
+            }
+        }
+        Ok(board)
+    }
+
+    // build a `rows` x `cols` board from its faces, given in reading order.
+    // each face must be a single letter; lowercase letters are uppercased
+    pub fn try_from_letters<S: AsRef<str>>(
+        rows: usize,
+        cols: usize,
+        letters: &[S],
+    ) -> Result<Board, BoardError> {
+        if rows == 0 || cols == 0 {
+            return Err(BoardError::EmptyDimensions);
+        }
+        let size = rows
+            .checked_mul(cols)
+            .ok_or_else(|| BoardError::BadCode(format!("{}x{}", rows, cols)))?;
+        if letters.len() != size {
+            return Err(BoardError::WrongLength {
+                expected: size,
+                actual: letters.len(),
+            });
+        }
+
+        let mut pieces = Vec::with_capacity(letters.len());
+        for (i, face) in letters.iter().enumerate() {
+            let face = face.as_ref();
+            let mut chars = face.chars();
+            let letter = match (chars.next(), chars.next()) {
+                (Some(c), None) => c,
+                (None, _) => return Err(BoardError::NonAlphabetic(face.to_string())),
+                (Some(_), Some(_)) => {
+                    if face.chars().all(char::is_alphabetic) {
+                        return Err(BoardError::MultiLetterFace(face.to_string()));
+                    }
+                    return Err(BoardError::NonAlphabetic(face.to_string()));
+                }
+            };
+            if !letter.is_alphabetic() {
+                return Err(BoardError::NonAlphabetic(face.to_string()));
+            }
+            // some letters (e.g. 'ß') uppercase to more than one character
+            let mut upper = letter.to_uppercase();
+            let letter = match (upper.next(), upper.next()) {
+                (Some(c), None) => c,
+                _ => return Err(BoardError::MultiLetterFace(face.to_string())),
+            };
+            pieces.push(Piece::new(letter, i, cols));
+        }
+
+        Ok(Board {
+            rows,
+            cols,
+            pieces,
+            neighbors: adjacency(rows, cols),
+            include_borders: false,
+            seed: None,
+        })
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    // the piece at (row, col), if that position is on the board
+    pub fn get(&self, row: usize, col: usize) -> Option<&Piece> {
+        if row >= self.rows || col >= self.cols {
+            return None;
+        }
+        self.pieces.get(row * self.cols + col)
+    }
+
+    // the piece at a 0-indexed position, counting left to right, top to bottom
+    pub fn piece(&self, index: usize) -> Option<&Piece> {
+        self.pieces.get(index)
+    }
+
+    // every piece along with its (row, col), in reading order
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize, &Piece)> + '_ {
+        self.pieces.iter().map(|p| (p.row, p.col, p))
+    }
+
+    // all letters on the board, in reading order
+    pub fn letters(&self) -> Vec<char> {
+        self.pieces.iter().map(|p| p.letter).collect()
+    }
+
+    // the board as "<rows>x<cols>:<letters>", e.g. "2x2:ABCD".
+    // `from_code` turns it back into the same board
+    pub fn code(&self) -> String {
+        let letters: String = self.letters().into_iter().collect();
+        format!("{}x{}:{}", self.rows, self.cols, letters)
+    }
+
+    // the seed the board was dealt from, None for boards built from letters
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    // the same board turned a quarter turn clockwise.
+    // every piece keeps its neighbors, so the same words can be played
+    pub fn rotated(&self) -> Board {
+        let (rows, cols) = (self.cols, self.rows);
+        let mut pieces = Vec::with_capacity(self.pieces.len());
+        for i in 0..(rows * cols) {
+            let (row, col) = idx(i, cols);
+            let letter = self.pieces[(self.rows - 1 - col) * self.cols + row].letter;
+            pieces.push(Piece::new(letter, i, cols));
+        }
+        Board {
+            rows,
+            cols,
+            pieces,
+            neighbors: adjacency(rows, cols),
+            include_borders: self.include_borders,
+            seed: None,
         }
     }
 
     // returns the first instance of a letter
     pub fn first_instance_of(&self, letter: char) -> Option<usize> {
-        self.pieces.iter().position(|ref x| x.letter == letter)
+        self.pieces.iter().position(|x| x.letter == letter)
+    }
+
+    // returns the index of every piece showing `letter`
+    pub fn get_all_instances_of(&self, letter: char) -> Vec<usize> {
+        self.pieces
+            .iter()
+            .enumerate()
+            .filter(|&(_, x)| x.letter == letter)
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    // indices of all pieces touching the piece at `index` (diagonals included).
+    // an index that is not on the board has no neighbors
+    pub fn neighbors(&self, index: usize) -> &[usize] {
+        match self.neighbors.get(index) {
+            Some(n) => n,
+            None => &[],
+        }
+    }
+
+    pub fn are_neighbors(&self, a: usize, b: usize) -> bool {
+        self.neighbors(a).contains(&b)
+    }
+
+    // every way `word` can be traced on the board, as lists of (row, col).
+    // empty if the word cannot be played
+    pub fn find_paths(&self, word: &str) -> Vec<Vec<(usize, usize)>> {
+        let mut paths = vec![];
+        if let Some(collection) = self.collect_instances(word) {
+            collect_paths(self, &collection, 0, &mut vec![], &mut paths, false);
+        }
+        paths
+            .into_iter()
+            .map(|path| path.iter().map(|&i| idx(i, self.cols)).collect())
+            .collect()
+    }
+
+    // like `find_paths`, but stops looking after the first path
+    pub fn has_path(&self, word: &str) -> bool {
+        let mut paths = vec![];
+        if let Some(collection) = self.collect_instances(word) {
+            collect_paths(self, &collection, 0, &mut vec![], &mut paths, true);
+        }
+        !paths.is_empty()
+    }
+
+    // get vector of vector of piece indices, one entry per letter of `word`.
+    // None when a letter is not on the board at all
+    fn collect_instances(&self, word: &str) -> Option<Vec<Vec<usize>>> {
+        let mut collection = vec![];
+        for letter in word.to_uppercase().chars() {
+            let instances = self.get_all_instances_of(letter);
+            if instances.is_empty() {
+                return None;
+            }
+            collection.push(instances);
+        }
+        if collection.is_empty() {
+            return None;
+        }
+        Some(collection)
+    }
+}
+
+// build the neighbor list of every position on a `rows` x `cols` grid
+fn adjacency(rows: usize, cols: usize) -> Vec<Vec<usize>> {
+    let mut neighbors = Vec::with_capacity(rows * cols);
+    for row in 0..rows {
+        for col in 0..cols {
+            let mut list = Vec::with_capacity(8);
+            // saturating_sub keeps us on the board at the top/left edges
+            for r in row.saturating_sub(1)..(row + 2).min(rows) {
+                for c in col.saturating_sub(1)..(col + 2).min(cols) {
+                    // same piece is not a neighbor
+                    if r == row && c == col {
+                        continue;
+                    }
+                    list.push(r * cols + c);
+                }
+            }
+            neighbors.push(list);
+        }
     }
+    neighbors
+}
+
+// walks every combination of instances in `collection`, pushing each complete
+// path of piece indices into `paths`.
+// `path` is the path walked so far and is used to ensure no duplicate pieces
+fn collect_paths(
+    board: &Board,
+    collection: &[Vec<usize>],
+    index: usize,
+    path: &mut Vec<usize>,
+    paths: &mut Vec<Vec<usize>>,
+    first_only: bool,
+) {
+    for &piece in collection[index].iter() {
+        // if piece is duplicate, not valid
+        if path.contains(&piece) {
+            continue;
+        }
+
+        // not valid if piece is not a neighbor
+        if let Some(&prev) = path.last() {
+            if !board.are_neighbors(prev, piece) {
+                continue;
+            }
+        }
 
-    pub fn get_all_instances_of(&self, letter: char) -> Vec<&Piece> {
-        self.pieces.iter().filter(|&x| x.letter == letter).collect()
+        path.push(piece);
+        if index < collection.len() - 1 {
+            // search subsequent pieces if not at last piece
+            collect_paths(board, collection, index + 1, path, paths, first_only);
+        } else {
+            // if we've made it this far
+            // then we are at the last piece and have a valid path!
+            paths.push(path.clone());
+        }
+        path.pop();
+
+        if first_only && !paths.is_empty() {
+            return;
+        }
     }
 }
 
+// xorshift is the same on every platform, and the constants keep
+// the seed from ever being all zeros, which it can't take
+fn seeded_rng(seed: u64) -> XorShiftRng {
+    XorShiftRng::from_seed([seed as u32, (seed >> 32) as u32, 0x9E37_79B9, 0x7F4A_7C15])
+}
+
 // TODO?: make this a public `board.print` function instead of implementing display?
 impl Display for Board {
-    // print all pieces sequentially
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        let border_length = BOARD_DIMENSIONS.pow(2) - BOARD_DIMENSIONS + 1;
+        self.write_grid(f, None)
+    }
+}
+
+// a board printed with one piece marked, e.g. `[A]` instead of ` A `
+pub struct Highlighted<'a> {
+    board: &'a Board,
+    index: usize,
+}
+
+impl<'a> Display for Highlighted<'a> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.board.write_grid(f, Some(self.index))
+    }
+}
+
+impl Board {
+    // display the board with the piece at (row, col) marked
+    pub fn highlight(&self, row: usize, col: usize) -> Highlighted<'_> {
+        Highlighted {
+            board: self,
+            index: row * self.cols + col,
+        }
+    }
+
+    // print all pieces sequentially
+    fn write_grid(&self, f: &mut Formatter, highlight: Option<usize>) -> fmt::Result {
+        // each piece is 3 characters wide plus its left border
+        let border_length = self.cols * 4 + 1;
         if self.include_borders {
             // write top border
             for _i in 0..border_length {
                 write!(f, "-")?;
             }
         }
-        write!(f, "\n")?;
+        writeln!(f)?;
         for piece in self.pieces.iter() {
             // piece includes left border and padding
             if self.include_borders {
-                write!(f, "|{}", piece)?;
+                write!(f, "|")?;
+            }
+            if highlight == Some(piece.index) {
+                write!(f, "[{}]", piece.letter)?;
             } else {
                 write!(f, "{}", piece)?;
             }
             // write right-border
-            if piece.col != 0 && piece.col % (BOARD_DIMENSIONS - 1) == 0 {
+            if piece.col == self.cols - 1 {
                 if self.include_borders {
-                    write!(f, "|\n")?;
+                    writeln!(f, "|")?;
                     for _i in 0..border_length {
                         write!(f, "-")?;
                     }
                 } else {
-                    write!(f, "\n")?;
+                    writeln!(f)?;
                 }
-                write!(f, "\n")?;
+                writeln!(f)?;
             }
         }
-        write!(f, "\n")
+        writeln!(f)
     }
 }
 
 impl Piece {
-    pub fn new(letter: char, index: i32) -> Piece {
-        let (row, col) = idx(index);
-        Piece { letter, row, col }
+    pub fn new(letter: char, index: usize, cols: usize) -> Piece {
+        let (row, col) = idx(index, cols);
+        Piece {
+            letter,
+            row,
+            col,
+            index,
+        }
     }
 
-    pub fn is_neighbor(&self, p: &Piece) -> bool {
-        // same piece is not neighbor
-        if self.row == p.row && self.col == p.col {
-            return false;
-        }
-        // if no more than 1 row and column away
-        if (self.row - p.row).abs() <= 1 && (self.col - p.col).abs() <= 1 {
-            return true;
-        }
-        false
+    pub fn letter(&self) -> char {
+        self.letter
+    }
+
+    pub fn row(&self) -> usize {
+        self.row
+    }
+
+    pub fn col(&self) -> usize {
+        self.col
+    }
+
+    // position on the board, counting left to right, top to bottom
+    pub fn index(&self) -> usize {
+        self.index
     }
 }
 
@@ -165,15 +571,12 @@ impl PartialEq for Piece {
     fn eq(&self, other: &Piece) -> bool {
         self.letter == other.letter && self.row == other.row && self.col == other.col
     }
-    fn ne(&self, other: &Piece) -> bool {
-        self.letter != other.letter || self.row != other.row || self.col != other.col
-    }
 }
 
 // get (row, col) tuple from index
 // i should be 0-indexed
-fn idx(i: i32) -> (i32, i32) {
-    (i / BOARD_DIMENSIONS, i % BOARD_DIMENSIONS)
+fn idx(i: usize, cols: usize) -> (usize, usize) {
+    (i / cols, i % cols)
 }
 
 #[cfg(test)]
@@ -182,23 +585,330 @@ mod tests {
     #[test]
     fn it_gets_the_right_index() {
         let expected = (0, 3);
-        let actual = idx(3);
+        let actual = idx(3, 4);
         assert_eq!(actual, expected);
 
         let expected = (1, 0);
-        let actual = idx(4);
+        let actual = idx(4, 4);
         assert_eq!(actual, expected);
 
         let expected = (1, 1);
-        let actual = idx(5);
+        let actual = idx(5, 4);
         assert_eq!(actual, expected);
 
         let expected = (1, 3);
-        let actual = idx(7);
+        let actual = idx(7, 4);
         assert_eq!(actual, expected);
 
         let expected = (3, 3);
-        let actual = idx(15);
+        let actual = idx(15, 4);
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn adjacency_corners_edges_and_middle() {
+        let neighbors = adjacency(4, 4);
+        assert_eq!(neighbors.len(), 16);
+        assert_eq!(neighbors[0], vec![1, 4, 5]);
+        assert_eq!(neighbors[3], vec![2, 6, 7]);
+        assert_eq!(neighbors[5], vec![0, 1, 2, 4, 6, 8, 9, 10]);
+        assert_eq!(neighbors[7], vec![2, 3, 6, 10, 11]);
+        assert_eq!(neighbors[15], vec![10, 11, 14]);
+    }
+
+    #[test]
+    fn adjacency_is_symmetric() {
+        let board = Board::new();
+        for a in 0..16 {
+            for &b in board.neighbors(a) {
+                assert!(board.are_neighbors(b, a), "{} -> {} is one-way", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn adjacency_non_square() {
+        let neighbors = adjacency(2, 3);
+        assert_eq!(neighbors[0], vec![1, 3, 4]);
+        assert_eq!(neighbors[2], vec![1, 4, 5]);
+        assert_eq!(neighbors[4], vec![0, 1, 2, 3, 5]);
+    }
+
+    #[test]
+    fn random_non_square() {
+        let board = Board::random(3, 5);
+        assert_eq!(board.rows(), 3);
+        assert_eq!(board.cols(), 5);
+        assert_eq!(board.letters().len(), 15);
+        assert!(board.letters().iter().all(|l| l.is_ascii_uppercase()));
+        assert_eq!(board.neighbors(14), &[8, 9, 13]);
+    }
+
+    #[test]
+    fn neighbors_off_the_board() {
+        let board = Board::new();
+        assert!(board.neighbors(16).is_empty());
+        assert!(!board.are_neighbors(3, 4));
+    }
+
+    // one face per character
+    fn faces(letters: &str) -> Vec<String> {
+        letters.chars().map(|c| c.to_string()).collect()
+    }
+
+    fn test_board() -> Board {
+        Board::try_from_letters(4, 4, &faces("TESTABCDEFGHIJKL")).unwrap()
+    }
+
+    #[test]
+    fn same_seed_same_board() {
+        let board = Board::from_seed(5, 4, 42);
+        assert_eq!(board.seed(), Some(42));
+        assert_eq!(Board::from_seed(5, 4, 42).letters(), board.letters());
+        assert_ne!(Board::from_seed(5, 4, 43).letters(), board.letters());
+    }
+
+    #[test]
+    fn every_die_lands_once() {
+        let dice = Dice::from_reader("pairs", &b"AB\nCD\nEF\nGH\n"[..]).unwrap();
+        let board = Board::from_dice(2, 2, &dice, 7);
+        assert_eq!(board.seed(), None);
+        assert_eq!(Board::from_dice(2, 2, &dice, 7).letters(), board.letters());
+
+        // each die shows one of its faces, and no die is used twice
+        let mut dice_used: Vec<usize> = board
+            .letters()
+            .iter()
+            .map(|&letter| dice.dice().iter().position(|die| die.contains(&letter)).unwrap())
+            .collect();
+        dice_used.sort();
+        assert_eq!(dice_used, vec![0, 1, 2, 3]);
+
+        // a bigger board rolls the set again
+        let one = Dice::from_reader("one", &b"AB\n"[..]).unwrap();
+        let board = Board::from_dice(2, 3, &one, 1);
+        assert!(board.letters().iter().all(|&c| c == 'A' || c == 'B'));
+    }
+
+    #[test]
+    fn code_round_trips() {
+        let board = test_board();
+        assert_eq!(board.code(), "4x4:TESTABCDEFGHIJKL");
+        let again = Board::from_code(&board.code()).unwrap();
+        assert_eq!(again.letters(), board.letters());
+        assert_eq!(again.seed(), None);
+
+        assert_eq!(
+            Board::from_code("4x4").unwrap_err(),
+            BoardError::BadCode(String::from("4x4"))
+        );
+        assert_eq!(
+            Board::from_code("2x2:ABC").unwrap_err(),
+            BoardError::WrongLength {
+                expected: 4,
+                actual: 3,
+            }
+        );
+
+        // too big to count the tiles of
+        let huge = "18446744073709551615x2:AB";
+        assert_eq!(
+            Board::from_code(huge).unwrap_err(),
+            BoardError::BadCode(String::from(huge))
+        );
+        assert_eq!(
+            Board::try_from_letters(usize::MAX, 2, &["A", "B"]).unwrap_err(),
+            BoardError::BadCode(String::from("18446744073709551615x2"))
+        );
+    }
+
+    #[test]
+    fn a_code_keeps_its_seed_only_if_the_seed_deals_it() {
+        let code = Board::from_seed(4, 4, 42).code();
+        let board = Board::from_code_and_seed(&code, Some(42)).unwrap();
+        assert_eq!(board.seed(), Some(42));
+        assert_eq!(board.code(), code);
+        let board = Board::from_code_and_seed(&code, Some(43)).unwrap();
+        assert_eq!(board.seed(), None);
+        assert_eq!(board.code(), code);
+    }
+
+    #[test]
+    fn try_from_letters_uppercases() {
+        let board = Board::try_from_letters(2, 2, &["a", "B", "c", "d"]).unwrap();
+        assert_eq!(board.letters(), vec!['A', 'B', 'C', 'D']);
+        assert_eq!(board.get(1, 0).map(|p| p.index()), Some(2));
+    }
+
+    #[test]
+    fn try_from_letters_non_square() {
+        let board = Board::try_from_letters(2, 3, &faces("ABCDEF")).unwrap();
+        assert_eq!(board.rows(), 2);
+        assert_eq!(board.cols(), 3);
+        assert_eq!(board.get(1, 2).map(|p| p.letter()), Some('F'));
+        assert!(board.are_neighbors(2, 4));
+        assert!(!board.are_neighbors(2, 3));
+        assert_eq!(format!("{}", board), "\n A  B  C \n\n D  E  F \n\n\n");
+    }
+
+    #[test]
+    fn rotated_quarter_turn() {
+        let board = Board::try_from_letters(2, 3, &faces("ABCDEF")).unwrap();
+        let rotated = board.rotated();
+        assert_eq!(rotated.rows(), 3);
+        assert_eq!(rotated.cols(), 2);
+        assert_eq!(rotated.letters().iter().collect::<String>(), "DAEBFC");
+        let full_turn = rotated.rotated().rotated().rotated();
+        assert_eq!(full_turn.letters(), board.letters());
+        assert!(rotated.has_path("ABFED"));
+    }
+
+    #[test]
+    fn highlight_marks_one_piece() {
+        let board = Board::try_from_letters(2, 3, &faces("ABCDEF")).unwrap();
+        assert_eq!(
+            format!("{}", board.highlight(1, 1)),
+            "\n A  B  C \n\n D [E] F \n\n\n"
+        );
+    }
+
+    #[test]
+    fn try_from_letters_wrong_length() {
+        let err = Board::try_from_letters(4, 4, &faces("TESTR")).unwrap_err();
+        assert_eq!(
+            err,
+            BoardError::WrongLength {
+                expected: 16,
+                actual: 5,
+            }
+        );
+    }
+
+    #[test]
+    fn try_from_letters_empty_dimensions() {
+        let empty: [&str; 0] = [];
+        assert_eq!(
+            Board::try_from_letters(0, 4, &empty).unwrap_err(),
+            BoardError::EmptyDimensions
+        );
+    }
+
+    #[test]
+    fn try_from_letters_non_alphabetic() {
+        assert_eq!(
+            Board::try_from_letters(1, 2, &["A", "3"]).unwrap_err(),
+            BoardError::NonAlphabetic(String::from("3"))
+        );
+        assert_eq!(
+            Board::try_from_letters(1, 2, &["A", ""]).unwrap_err(),
+            BoardError::NonAlphabetic(String::new())
+        );
+        assert_eq!(
+            Board::try_from_letters(1, 2, &["A", "B!"]).unwrap_err(),
+            BoardError::NonAlphabetic(String::from("B!"))
+        );
+    }
+
+    #[test]
+    fn try_from_letters_multi_letter_face() {
+        assert_eq!(
+            Board::try_from_letters(1, 2, &["Qu", "A"]).unwrap_err(),
+            BoardError::MultiLetterFace(String::from("Qu"))
+        );
+    }
+
+    #[test]
+    fn grid_accessors() {
+        let board = test_board();
+        assert_eq!(board.rows(), 4);
+        assert_eq!(board.cols(), 4);
+        assert_eq!(board.get(1, 2).map(|p| p.letter()), Some('C'));
+        assert_eq!(board.get(1, 2).map(|p| p.index()), Some(6));
+        assert!(board.get(4, 0).is_none());
+        assert!(board.get(0, 4).is_none());
+        assert_eq!(board.letters().iter().collect::<String>(), "TESTABCDEFGHIJKL");
+        let coords: Vec<(usize, usize)> = board.iter().map(|(r, c, _)| (r, c)).collect();
+        assert_eq!(coords[5], (1, 1));
+        assert_eq!(coords[15], (3, 3));
+    }
+
+    #[test]
+    fn find_paths_single_letter() {
+        // trivial case, but a single letter on the board always has a path
+        let board = test_board();
+        assert_eq!(board.find_paths("a"), vec![vec![(1, 0)]]);
+    }
+
+    #[test]
+    fn find_paths_non_adjacent_pieces() {
+        let board = test_board();
+        assert!(board.find_paths("TH").is_empty());
+        assert!(!board.has_path("TH"));
+    }
+
+    #[test]
+    fn find_paths_adjacent_pieces() {
+        let board = test_board();
+        assert_eq!(board.find_paths("TE"), vec![vec![(0, 0), (0, 1)]]);
+        assert!(board.has_path("te"));
+    }
+
+    #[test]
+    fn find_paths_same_pieces() {
+        // should not be allowed to use same piece twice
+        let board = test_board();
+        assert!(board.find_paths("TET").is_empty());
+    }
+
+    #[test]
+    fn find_paths_last_piece_wrong() {
+        let board = test_board();
+        assert!(board.find_paths("TEL").is_empty());
+    }
+
+    #[test]
+    fn find_paths_letter_not_on_board() {
+        let board = test_board();
+        assert!(board.find_paths("TEZ").is_empty());
+        assert!(board.find_paths("").is_empty());
+    }
+
+    #[test]
+    fn find_paths_every_route() {
+        // B touches both E's
+        let board = test_board();
+        assert_eq!(
+            board.find_paths("BE"),
+            vec![vec![(1, 1), (0, 1)], vec![(1, 1), (2, 0)]]
+        );
+    }
+
+    #[test]
+    fn find_paths_try_all_combinations() {
+        // should be able to try all possible combinations when most
+        // instances of each letter lead nowhere
+        let board = Board::try_from_letters(4, 4, &faces(
+            "AXAB\
+             BXCX\
+             CXXA\
+             DXXX",
+        )).unwrap();
+        assert_eq!(
+            board.find_paths("ABCD"),
+            vec![vec![(0, 0), (1, 0), (2, 0), (3, 0)]]
+        );
+    }
+
+    #[test]
+    fn find_paths_backtracks_cleanly() {
+        // a dead end through a piece must not leave it marked as used
+        // when the search comes back around to it another way
+        let board = Board::try_from_letters(4, 4, &faces(
+            "ABXX\
+             XBXX\
+             XXXX\
+             XXXX",
+        )).unwrap();
+        assert!(board.has_path("ABB"));
+    }
 }
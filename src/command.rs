@@ -0,0 +1,119 @@
+//! Colon-commands a player can type during a round instead of a guess
+
+use hint::HintKind;
+
+// what each command does, shown by `:help`
+pub const HELP: &[(&str, &str)] = &[
+    (":hint", "get a hint: add lengths, letters, tile or longest to pick one"),
+    (":shuffle", "turn the board a quarter turn"),
+    (":pause", "stop the clock until you press enter"),
+    (":time", "show the time left"),
+    (":score", "show your score so far"),
+    (":list", "list the words you've found"),
+    (":undo", "take back your last guess"),
+    (":save", "put the round aside for `boggle resume`"),
+    (":quit", "end the round now"),
+    (":help", "show this list"),
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    // a random kind of hint when None
+    Hint(Option<HintKind>),
+    Shuffle,
+    Pause,
+    Time,
+    Score,
+    List,
+    Undo,
+    Save,
+    Quit,
+    Help,
+    // anything else starting with a colon
+    Unknown(String),
+}
+
+impl Command {
+    // None if `line` is a guess rather than a command
+    pub fn parse(line: &str) -> Option<Command> {
+        let line = line.trim();
+        if !line.starts_with(':') {
+            return None;
+        }
+        let lower = line[1..].trim().to_lowercase();
+        let mut words = lower.split_whitespace();
+        let name = words.next().unwrap_or("");
+        let argument = words.next();
+        if words.next().is_some() {
+            return Some(Command::Unknown(String::from(line)));
+        }
+        let command = match (name, argument) {
+            ("hint", None) => Command::Hint(None),
+            ("hint", Some(kind)) => match HintKind::from_name(kind) {
+                Some(kind) => Command::Hint(Some(kind)),
+                None => Command::Unknown(String::from(line)),
+            },
+            // nothing else takes an argument
+            (_, Some(_)) => Command::Unknown(String::from(line)),
+            (name, None) => match name {
+                "shuffle" => Command::Shuffle,
+                "pause" => Command::Pause,
+                "time" => Command::Time,
+                "score" => Command::Score,
+                "list" => Command::List,
+                "undo" => Command::Undo,
+                "save" => Command::Save,
+                "quit" => Command::Quit,
+                "help" => Command::Help,
+                _ => Command::Unknown(String::from(line)),
+            },
+        };
+        Some(command)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn guesses_are_not_commands() {
+        assert_eq!(Command::parse("cat\n"), None);
+        assert_eq!(Command::parse(""), None);
+        assert_eq!(Command::parse("ca:t"), None);
+    }
+
+    #[test]
+    fn parses_commands() {
+        assert_eq!(Command::parse(":hint\n"), Some(Command::Hint(None)));
+        assert_eq!(
+            Command::parse(":hint Tile"),
+            Some(Command::Hint(Some(HintKind::Tile)))
+        );
+        assert_eq!(
+            Command::parse(":hint answer"),
+            Some(Command::Unknown(String::from(":hint answer")))
+        );
+        assert_eq!(
+            Command::parse(":time please"),
+            Some(Command::Unknown(String::from(":time please")))
+        );
+        assert_eq!(Command::parse("  :UNDO "), Some(Command::Undo));
+        assert_eq!(Command::parse(": quit"), Some(Command::Quit));
+        assert_eq!(
+            Command::parse(":fly"),
+            Some(Command::Unknown(String::from(":fly")))
+        );
+        assert_eq!(Command::parse(":"), Some(Command::Unknown(String::from(":"))));
+    }
+
+    #[test]
+    fn help_covers_every_command() {
+        for &(name, _) in HELP {
+            match Command::parse(name) {
+                Some(Command::Unknown(_)) | None => panic!("{} is not a command", name),
+                Some(_) => (),
+            }
+        }
+    }
+}
@@ -0,0 +1,259 @@
+//! Output for a game session.
+//! A terminal can move the cursor and use colors, plain output just writes text.
+//! Plain output is used whenever stdout is not a capable terminal
+//! (pipes, CI logs, `TERM=dumb`), so the game never depends on cursor movement
+
+use std::env;
+use std::io::{self, IsTerminal, Write};
+
+use term::terminfo::TermInfo;
+use term::{self, Terminal};
+
+// blank lines that push the screen out of sight when it can't be cleared
+const BLANK_LINES: usize = 100;
+
+pub struct Console<W: Write> {
+    out: Output<W>,
+    // turned off the first time the terminal refuses to move the cursor
+    can_move_cursor: bool,
+    // players can turn colors off even on a terminal that has them
+    colors: bool,
+    // what clears the screen, from terminfo, if the terminal says
+    clear: Option<Vec<u8>>,
+}
+
+enum Output<W: Write> {
+    Terminal(Box<dyn Terminal<Output = W> + Send>),
+    Plain(W),
+}
+
+impl Console<io::Stdout> {
+    // pick terminal or plain output for stdout based on what it's attached to
+    pub fn stdout() -> Console<io::Stdout> {
+        let term_var = env::var("TERM").ok();
+        if wants_plain(io::stdout().is_terminal(), term_var.as_deref()) {
+            return Console::plain(io::stdout());
+        }
+        match term::stdout() {
+            Some(t) => Console::terminal(t),
+            None => Console::plain(io::stdout()),
+        }
+    }
+}
+
+impl<W: Write> Console<W> {
+    pub fn plain(out: W) -> Console<W> {
+        Console {
+            out: Output::Plain(out),
+            can_move_cursor: false,
+            colors: false,
+            clear: None,
+        }
+    }
+
+    pub fn terminal(terminal: Box<dyn Terminal<Output = W> + Send>) -> Console<W> {
+        Console {
+            out: Output::Terminal(terminal),
+            can_move_cursor: true,
+            colors: true,
+            clear: TermInfo::from_env().ok().and_then(|info| clear_sequence(&info)),
+        }
+    }
+
+    // true when output never contains cursor movement or colors
+    pub fn is_plain(&self) -> bool {
+        match self.out {
+            Output::Terminal(_) => !self.can_move_cursor,
+            Output::Plain(_) => true,
+        }
+    }
+
+    // colors are only ever shown on a terminal
+    pub fn set_colors(&mut self, colors: bool) {
+        self.colors = colors;
+    }
+
+    // remove the line above the cursor, e.g. a prompt that has been answered.
+    // does nothing on plain output or on terminals that can't move the cursor
+    pub fn clean_prev_line(&mut self) -> io::Result<()> {
+        if !self.can_move_cursor {
+            return Ok(());
+        }
+        if let Output::Terminal(ref mut t) = self.out {
+            let moved = t.cursor_up().and_then(|_| t.delete_line());
+            match moved {
+                Ok(()) => (),
+                Err(term::Error::Io(e)) => return Err(e),
+                Err(_) => self.can_move_cursor = false,
+            }
+        }
+        Ok(())
+    }
+
+    // wipe the terminal so nothing on it can be seen, e.g. the board
+    // before passing the keyboard on. a terminal that can't be cleared gets
+    // enough blank lines to scroll it away, plain output has nothing to hide
+    pub fn clear_screen(&mut self) -> io::Result<()> {
+        if let Output::Terminal(ref mut t) = self.out {
+            match self.clear {
+                Some(ref clear) if self.can_move_cursor => t.write_all(clear)?,
+                _ => t.write_all(&[b'\n'; BLANK_LINES])?,
+            }
+            t.flush()?;
+        }
+        Ok(())
+    }
+
+    // yellow text on a terminal, plain text everywhere else
+    pub fn warn(&mut self, message: &str) -> io::Result<()> {
+        match self.out {
+            Output::Terminal(ref mut t) if self.colors => {
+                let colored = match t.fg(term::color::YELLOW) {
+                    Ok(()) => true,
+                    Err(term::Error::Io(e)) => return Err(e),
+                    Err(_) => false,
+                };
+                write!(t, "{}", message)?;
+                if colored {
+                    if let Err(term::Error::Io(e)) = t.reset() {
+                        return Err(e);
+                    }
+                }
+                Ok(())
+            }
+            Output::Terminal(ref mut t) => write!(t, "{}", message),
+            Output::Plain(ref mut out) => write!(out, "{}", message),
+        }
+    }
+
+    pub fn get_ref(&self) -> &W {
+        match self.out {
+            Output::Terminal(ref t) => t.get_ref(),
+            Output::Plain(ref out) => out,
+        }
+    }
+}
+
+// the terminal's `clear`, then `E3` to wipe the scrollback too where there is one
+fn clear_sequence(info: &TermInfo) -> Option<Vec<u8>> {
+    let mut clear = vec![];
+    info.apply_cap("clear", &[], &mut clear).ok()?;
+    let _ = info.apply_cap("E3", &[], &mut clear);
+    Some(clear)
+}
+
+impl<W: Write> Write for Console<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self.out {
+            Output::Terminal(ref mut t) => t.write(buf),
+            Output::Plain(ref mut out) => out.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self.out {
+            Output::Terminal(ref mut t) => t.flush(),
+            Output::Plain(ref mut out) => out.flush(),
+        }
+    }
+}
+
+// pipes, files and dumb terminals get plain output
+fn wants_plain(is_tty: bool, term_var: Option<&str>) -> bool {
+    !is_tty || term_var == Some("dumb")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use term::terminfo::{TermInfo, TerminfoTerminal};
+
+    // a terminal whose terminfo entry has no capabilities at all
+    fn incapable_terminal() -> Console<Vec<u8>> {
+        let info = TermInfo {
+            names: vec![String::from("nothing")],
+            bools: HashMap::new(),
+            numbers: HashMap::new(),
+            strings: HashMap::new(),
+        };
+        Console::terminal(Box::new(TerminfoTerminal::new_with_terminfo(vec![], info)))
+    }
+
+    #[test]
+    fn wants_plain_when_not_a_tty_or_dumb() {
+        assert!(wants_plain(false, Some("xterm")));
+        assert!(wants_plain(true, Some("dumb")));
+        assert!(!wants_plain(true, Some("xterm")));
+        assert!(!wants_plain(true, None));
+    }
+
+    #[test]
+    fn plain_never_moves_the_cursor() {
+        let mut console = Console::plain(vec![]);
+        write!(console, "prompt").unwrap();
+        console.clean_prev_line().unwrap();
+        console.warn("careful").unwrap();
+        assert!(console.is_plain());
+        assert_eq!(console.get_ref(), b"promptcareful");
+    }
+
+    #[test]
+    fn incapable_terminal_falls_back_to_plain() {
+        let mut console = incapable_terminal();
+        assert!(!console.is_plain());
+        console.clean_prev_line().unwrap();
+        assert!(console.is_plain());
+        console.warn("careful").unwrap();
+        assert_eq!(console.get_ref(), b"careful");
+    }
+
+    #[test]
+    fn clearing_uses_terminfo_or_scrolls_away() {
+        let mut strings = HashMap::new();
+        strings.insert("clear", b"<clear>".to_vec());
+        let info = TermInfo {
+            names: vec![String::from("clearable")],
+            bools: HashMap::new(),
+            numbers: HashMap::new(),
+            strings,
+        };
+        assert_eq!(clear_sequence(&info), Some(b"<clear>".to_vec()));
+
+        let mut console = incapable_terminal();
+        console.clear = clear_sequence(&info);
+        console.clear_screen().unwrap();
+        assert_eq!(console.get_ref(), b"<clear>");
+
+        let mut console = incapable_terminal();
+        console.clear = None;
+        console.clear_screen().unwrap();
+        assert_eq!(console.get_ref().len(), BLANK_LINES);
+        assert!(console.get_ref().iter().all(|&b| b == b'\n'));
+
+        let mut console = Console::plain(vec![]);
+        console.clear_screen().unwrap();
+        assert!(console.get_ref().is_empty());
+    }
+
+    #[test]
+    fn colors_can_be_turned_off() {
+        let mut strings = HashMap::new();
+        strings.insert("setaf", b"<%p1%d>".to_vec());
+        strings.insert("setab", b"".to_vec());
+        strings.insert("sgr0", b"</>".to_vec());
+        let mut numbers = HashMap::new();
+        numbers.insert("colors", 8);
+        let info = TermInfo {
+            names: vec![String::from("colorful")],
+            bools: HashMap::new(),
+            numbers,
+            strings,
+        };
+        let mut console = Console::terminal(Box::new(TerminfoTerminal::new_with_terminfo(vec![], info)));
+        console.warn("careful").unwrap();
+        console.set_colors(false);
+        console.warn(" again").unwrap();
+        assert_eq!(console.get_ref(), b"<3>careful</> again");
+    }
+}
@@ -0,0 +1,101 @@
+//! Sets of letter dice to deal boards from, like the ones in the box.
+//! A dice file has one die per line, its faces written out as letters,
+//! e.g. `AAEEGN` or `A A E E G N`. Blank lines are skipped
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+
+// the 16 dice of the 4x4 game. the Qu face is a plain Q here,
+// since faces are single letters
+const CLASSIC: [&str; 16] = [
+    "AAEEGN", "ABBJOO", "ACHOPS", "AFFKPS", "AOOTTW", "CIMOTU", "DEILRX", "DELRVY", "DISTTY",
+    "EEGHNW", "EEINSU", "EHRTVW", "EIOSST", "ELRTTY", "HIMNQU", "HLNNRZ",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dice {
+    // what the set is called in results, e.g. the file it came from
+    name: String,
+    // each die's faces, uppercase
+    dice: Vec<Vec<char>>,
+}
+
+impl Dice {
+    pub fn classic() -> Dice {
+        Dice::from_lines("classic", CLASSIC.iter()).expect("the classic dice are letters")
+    }
+
+    pub fn from_reader<R: BufRead>(name: &str, reader: R) -> io::Result<Dice> {
+        let lines = reader.lines().collect::<io::Result<Vec<String>>>()?;
+        Dice::from_lines(name, lines.iter())
+    }
+
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Dice> {
+        let path = path.as_ref();
+        let file = File::open(path)?;
+        Dice::from_reader(&path.display().to_string(), BufReader::new(file))
+    }
+
+    fn from_lines<I, S>(name: &str, lines: I) -> io::Result<Dice>
+    where
+        I: Iterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidData, message);
+        let mut dice = vec![];
+        for (n, line) in lines.enumerate() {
+            let faces: Vec<char> = line.as_ref().chars().filter(|c| !c.is_whitespace()).collect();
+            if faces.is_empty() {
+                continue;
+            }
+            if let Some(face) = faces.iter().find(|c| !c.is_alphabetic()) {
+                return Err(invalid(format!("line {}: \"{}\" is not a letter", n + 1, face)));
+            }
+            dice.push(faces.iter().flat_map(|c| c.to_uppercase()).collect());
+        }
+        if dice.is_empty() {
+            return Err(invalid(format!("{} has no dice in it", name)));
+        }
+        Ok(Dice {
+            name: name.to_string(),
+            dice,
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn dice(&self) -> &[Vec<char>] {
+        &self.dice
+    }
+
+    pub fn len(&self) -> usize {
+        self.dice.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.dice.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn one_die_per_line() {
+        let dice = Dice::from_reader("mine", &b"aaeegn\n\nA B B J O O\n"[..]).unwrap();
+        assert_eq!(dice.name(), "mine");
+        assert_eq!(dice.dice(), &[
+            vec!['A', 'A', 'E', 'E', 'G', 'N'],
+            vec!['A', 'B', 'B', 'J', 'O', 'O'],
+        ]);
+        assert_eq!(Dice::classic().len(), 16);
+
+        let error = Dice::from_reader("bad", &b"AAEEGN\nAB?JOO\n"[..]).unwrap_err();
+        assert_eq!(error.to_string(), "line 2: \"?\" is not a letter");
+        assert!(Dice::from_reader("empty", &b"\n"[..]).is_err());
+    }
+}
@@ -0,0 +1,109 @@
+//! Word list used to check guesses and to solve boards.
+//! Words are stored uppercase in sorted order,
+//! which lets the solver ask "does any word start with this?" cheaply
+
+use std::collections::BTreeSet;
+use std::collections::btree_set;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+
+#[derive(Debug, Clone, Default)]
+pub struct Dictionary {
+    words: BTreeSet<String>,
+}
+
+impl Dictionary {
+    pub fn from_words<I, S>(words: I) -> Dictionary
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let words = words
+            .into_iter()
+            .map(|w| w.as_ref().trim().to_uppercase())
+            .filter(|w| !w.is_empty())
+            .collect();
+        Dictionary { words }
+    }
+
+    // one word per line, blank lines are skipped and so are lines that
+    // aren't UTF-8, rather than losing the whole list over one bad line
+    pub fn from_reader<R: BufRead>(reader: R) -> io::Result<Dictionary> {
+        let mut words = vec![];
+        for line in reader.split(b'\n') {
+            if let Ok(word) = String::from_utf8(line?) {
+                words.push(word);
+            }
+        }
+        Ok(Dictionary::from_words(words))
+    }
+
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Dictionary> {
+        let file = File::open(path)?;
+        Dictionary::from_reader(BufReader::new(file))
+    }
+
+    // case-insensitive lookup
+    pub fn contains(&self, word: &str) -> bool {
+        self.words.contains(&word.to_uppercase())
+    }
+
+    // true if any word in the dictionary starts with `prefix` (case-insensitive).
+    // the first word sorted at or after the prefix is the only candidate
+    pub fn has_prefix(&self, prefix: &str) -> bool {
+        let prefix = prefix.to_uppercase();
+        match self.words.range(prefix.clone()..).next() {
+            Some(word) => word.starts_with(&prefix),
+            None => false,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    // all words, uppercase and in alphabetical order
+    pub fn iter(&self) -> btree_set::Iter<'_, String> {
+        self.words.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_reader_uppercases_and_skips_blanks() {
+        let dict = Dictionary::from_reader("cat\n\nDog\n  bird \n".as_bytes()).unwrap();
+        assert_eq!(dict.len(), 3);
+        assert!(dict.contains("CAT"));
+        assert!(dict.contains("dog"));
+        assert!(dict.contains("Bird"));
+        assert!(!dict.contains(""));
+    }
+
+    #[test]
+    fn from_reader_skips_lines_that_arent_utf8() {
+        let dict = Dictionary::from_reader(&b"cat\r\ncaf\xe9\ndog\n"[..]).unwrap();
+        assert_eq!(dict.len(), 2);
+        assert!(dict.contains("cat"));
+        assert!(dict.contains("dog"));
+    }
+
+    #[test]
+    fn has_prefix() {
+        let dict = Dictionary::from_words(vec!["cart", "cat", "dog"]);
+        assert!(dict.has_prefix("ca"));
+        assert!(dict.has_prefix("CAR"));
+        assert!(dict.has_prefix("cat"));
+        assert!(dict.has_prefix(""));
+        assert!(!dict.has_prefix("cats"));
+        assert!(!dict.has_prefix("do g"));
+        assert!(!dict.has_prefix("e"));
+    }
+}
@@ -0,0 +1,351 @@
+//! `--output results.json` or `--output results.csv`: every round played,
+//! written out in full for spreadsheets and scripts. The file is rewritten
+//! after each round, so it always holds the whole sitting so far
+
+use std::fmt::{self, Display, Formatter};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use serde_json;
+
+use game::Game;
+use history::{self, GameRecord, GuessRecord};
+use rejection::Rejection;
+use rules::Rules;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    Json,
+    Csv,
+}
+
+impl Format {
+    // going by the file's extension
+    pub fn from_path(path: &Path) -> Option<Format> {
+        let extension = path.extension()?.to_str()?.to_lowercase();
+        match extension.as_str() {
+            "json" => Some(Format::Json),
+            "csv" => Some(Format::Csv),
+            _ => None,
+        }
+    }
+}
+
+// what became of a word
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Outcome {
+    Valid,
+    Invalid,
+    NotInDictionary,
+    // guessed again after it counted once
+    Repeated,
+    // on the board and in the dictionary, but nobody guessed it
+    Missed,
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let name = match *self {
+            Outcome::Valid => "valid",
+            Outcome::Invalid => "invalid",
+            Outcome::NotInDictionary => "not_in_dictionary",
+            Outcome::Repeated => "repeated",
+            Outcome::Missed => "missed",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ReportedGuess {
+    pub word: String,
+    pub bucket: Outcome,
+    pub points: usize,
+    // why it didn't count, in words
+    pub reason: Option<String>,
+    // seconds into the round
+    pub at_secs: Option<u64>,
+}
+
+impl ReportedGuess {
+    fn new(guess: GuessRecord) -> ReportedGuess {
+        let bucket = match guess.rejection {
+            None => Outcome::Valid,
+            Some(Rejection::NotInDictionary) => Outcome::NotInDictionary,
+            Some(Rejection::Duplicate) => Outcome::Repeated,
+            Some(_) => Outcome::Invalid,
+        };
+        ReportedGuess {
+            word: guess.word,
+            bucket,
+            points: guess.points,
+            reason: guess.rejection.map(|r| r.to_string()),
+            at_secs: guess.at_secs,
+        }
+    }
+}
+
+// one round, as written to the file
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Report {
+    // unix time, in seconds, when the round ended
+    pub played_at: u64,
+    pub player: String,
+    // see `Board::code`
+    pub board: String,
+    pub seed: Option<u64>,
+    pub rules: Rules,
+    pub minutes: f64,
+    pub seconds_played: u64,
+    // in the order they were made
+    pub guesses: Vec<ReportedGuess>,
+    pub penalty: usize,
+    pub score: usize,
+    // lowercase and sorted, empty without a dictionary
+    pub missed: Vec<String>,
+}
+
+impl Report {
+    pub fn new(game: &mut Game, minutes: f64, seconds_played: u64, played_at: u64) -> Report {
+        let record = GameRecord::new(game, minutes, seconds_played, played_at);
+        Report::from_record(record, missed(game))
+    }
+
+    // the round as it went in the history, plus the words nobody found
+    pub fn from_record(record: GameRecord, missed: Vec<String>) -> Report {
+        Report {
+            played_at: record.played_at,
+            player: record.player,
+            board: record.board,
+            seed: record.seed,
+            rules: record.rules,
+            minutes: record.minutes,
+            seconds_played: record.seconds_played,
+            guesses: record.guesses.into_iter().map(ReportedGuess::new).collect(),
+            penalty: record.penalty,
+            score: record.score,
+            missed,
+        }
+    }
+}
+
+// words on the board nobody found, lowercase and sorted, none without a
+// dictionary. `game` only works out its solutions the first time
+pub fn missed(game: &mut Game) -> Vec<String> {
+    let found: Vec<String> = game.guesses
+        .valid()
+        .iter()
+        .map(|g| g.word().to_uppercase())
+        .collect();
+    match game.solutions() {
+        Some(words) => words
+            .iter()
+            .filter(|w| !found.contains(w))
+            .map(|w| w.to_lowercase())
+            .collect(),
+        None => vec![],
+    }
+}
+
+// the file the rounds go to
+pub struct Export {
+    path: PathBuf,
+    format: Format,
+    reports: Vec<Report>,
+}
+
+impl Export {
+    // None if the file isn't a .json or .csv
+    pub fn new<P: Into<PathBuf>>(path: P) -> Option<Export> {
+        let path = path.into();
+        let format = Format::from_path(&path)?;
+        Some(Export {
+            path,
+            format,
+            reports: vec![],
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    // add a round and write the file again. a failed write doesn't lose
+    // earlier rounds, see `history::write_atomically`
+    pub fn add(&mut self, report: Report) -> io::Result<()> {
+        self.reports.push(report);
+        let reports = &self.reports;
+        let format = self.format;
+        history::write_atomically(&self.path, |file| match format {
+            Format::Json => write_json(reports, file),
+            Format::Csv => write_csv(reports, file),
+        })
+    }
+}
+
+// all rounds as one JSON array
+pub fn write_json<W: Write>(reports: &[Report], out: &mut W) -> io::Result<()> {
+    serde_json::to_writer_pretty(&mut *out, reports).map_err(io::Error::other)?;
+    writeln!(out)
+}
+
+const CSV_HEADER: &str = "round,played_at,player,board,seed,minutes,seconds_played,\
+                          min_word_length,hint_points,hint_seconds,practice,\
+                          word,bucket,points,reason,at_secs,penalty,score";
+
+// one row per guess, and one per missed word with the bucket "missed".
+// the round's details are repeated on every row so any row stands alone
+pub fn write_csv<W: Write>(reports: &[Report], out: &mut W) -> io::Result<()> {
+    writeln!(out, "{}", CSV_HEADER)?;
+    for (round, report) in reports.iter().enumerate() {
+        let rules = &report.rules;
+        let round_fields = [
+            (round + 1).to_string(),
+            report.played_at.to_string(),
+            csv_field(&report.player),
+            csv_field(&report.board),
+            report.seed.map(|s| s.to_string()).unwrap_or_default(),
+            report.minutes.to_string(),
+            report.seconds_played.to_string(),
+            rules.min_word_length.to_string(),
+            rules.hint_points.to_string(),
+            rules.hint_seconds.to_string(),
+            rules.practice.to_string(),
+        ]
+        .join(",");
+        let missed = report.missed.iter().map(|word| ReportedGuess {
+            word: word.clone(),
+            bucket: Outcome::Missed,
+            points: 0,
+            reason: None,
+            at_secs: None,
+        });
+        for guess in report.guesses.iter().cloned().chain(missed) {
+            writeln!(
+                out,
+                "{},{},{},{},{},{},{},{}",
+                round_fields,
+                csv_field(&guess.word),
+                guess.bucket,
+                guess.points,
+                csv_field(guess.reason.as_deref().unwrap_or("")),
+                guess.at_secs.map(|s| s.to_string()).unwrap_or_default(),
+                report.penalty,
+                report.score
+            )?;
+        }
+    }
+    Ok(())
+}
+
+// quoted when it has to be, with quotes doubled
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use board::Board;
+    use dictionary::Dictionary;
+    use game::Player;
+    use std::fs;
+    use std::sync::Arc;
+
+    fn game() -> Game {
+        let board = Board::cats_and_dog();
+        let dictionary = Dictionary::from_words(vec!["cat", "cats", "dog", "act"]);
+        let mut game = Game::new(board, Player::new(String::from("eric")), Some(Arc::new(dictionary)));
+        let _ = game.add_guess_at(String::from("cats"), 3);
+        let _ = game.add_guess_at(String::from("tac"), 5);
+        let _ = game.add_guess_at(String::from("cats"), 8);
+        let _ = game.add_guess_at(String::from("tat"), 9);
+        game
+    }
+
+    #[test]
+    fn formats_come_from_the_extension() {
+        assert_eq!(Format::from_path(Path::new("out/results.JSON")), Some(Format::Json));
+        assert_eq!(Format::from_path(Path::new("results.csv")), Some(Format::Csv));
+        assert_eq!(Format::from_path(Path::new("results.txt")), None);
+        assert_eq!(Format::from_path(Path::new("results")), None);
+    }
+
+    #[test]
+    fn reports_have_every_guess_and_the_missed_words() {
+        let report = Report::new(&mut game(), 3.0, 42, 1_700_000_000);
+        let buckets: Vec<(&str, Outcome)> = report
+            .guesses
+            .iter()
+            .map(|g| (g.word.as_str(), g.bucket))
+            .collect();
+        assert_eq!(
+            buckets,
+            vec![
+                ("cats", Outcome::Valid),
+                ("tac", Outcome::NotInDictionary),
+                ("cats", Outcome::Repeated),
+                ("tat", Outcome::Invalid),
+            ]
+        );
+        assert_eq!(report.missed, vec!["cat", "dog"]);
+        assert_eq!(report.score, 1);
+        assert_eq!(report.board, "4x4:CATSXXXXDOGXXXXX");
+
+        let mut json = vec![];
+        write_json(&[report], &mut json).unwrap();
+        let json = String::from_utf8(json).unwrap();
+        assert!(json.starts_with("[\n  {\n    \"played_at\": 1700000000,"));
+        assert!(json.contains("\"bucket\": \"not_in_dictionary\""));
+        assert!(json.contains("\"missed\": [\n      \"cat\",\n      \"dog\"\n    ]"));
+    }
+
+    #[test]
+    fn csv_has_a_row_per_word() {
+        let report = Report::new(&mut game(), 3.0, 42, 1_700_000_000);
+        let mut csv = vec![];
+        write_csv(&[report.clone(), report], &mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], CSV_HEADER);
+        // four guesses and two missed words a round
+        assert_eq!(lines.len(), 13);
+        let round = "1,1700000000,eric,4x4:CATSXXXXDOGXXXXX,,3,42,0,0,0,false";
+        assert_eq!(lines[1], format!("{},cats,valid,1,,3,0,1", round));
+        assert_eq!(
+            lines[4],
+            format!(
+                "{},tat,invalid,0,\"it would use the tile at row 1, column 3 twice\",9,0,1",
+                round
+            )
+        );
+        assert_eq!(lines[5], format!("{},cat,missed,0,,,0,1", round));
+        assert!(lines[7].starts_with("2,1700000000,eric,"));
+    }
+
+    #[test]
+    fn fields_are_quoted_when_needed() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a, b"), "\"a, b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+
+    #[test]
+    fn export_rewrites_the_whole_file() {
+        let dir = ::std::env::temp_dir().join(format!("boggle-export-{}", ::std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("results.csv");
+        let mut export = Export::new(&path).unwrap();
+        export.add(Report::new(&mut game(), 3.0, 42, 1)).unwrap();
+        export.add(Report::new(&mut game(), 3.0, 42, 2)).unwrap();
+        let csv = fs::read_to_string(&path).unwrap();
+        assert_eq!(csv.lines().count(), 13);
+        assert!(Export::new(dir.join("results.txt")).is_none());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,83 @@
+//! How common words are, from a word frequency list: one word per line,
+//! the commonest first. Anything after the word on a line, like a count,
+//! is ignored, so most published lists work as they are.
+//! The computer player knows the common words first
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+
+#[derive(Debug, Clone, Default)]
+pub struct Frequencies {
+    // uppercase word to its place in the list, 0 for the commonest
+    ranks: HashMap<String, usize>,
+}
+
+impl Frequencies {
+    // `words` commonest first, a word listed twice keeps its first place
+    pub fn from_words<I, S>(words: I) -> Frequencies
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut ranks = HashMap::new();
+        let words = words
+            .into_iter()
+            .map(|w| w.as_ref().trim().to_uppercase())
+            .filter(|w| !w.is_empty());
+        for word in words {
+            let rank = ranks.len();
+            ranks.entry(word).or_insert(rank);
+        }
+        Frequencies { ranks }
+    }
+
+    // like `Dictionary::from_reader`, blank lines and lines that
+    // aren't UTF-8 are skipped
+    pub fn from_reader<R: BufRead>(reader: R) -> io::Result<Frequencies> {
+        let mut words = vec![];
+        for line in reader.split(b'\n') {
+            if let Ok(line) = String::from_utf8(line?) {
+                if let Some(word) = line.split_whitespace().next() {
+                    words.push(word.to_string());
+                }
+            }
+        }
+        Ok(Frequencies::from_words(words))
+    }
+
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Frequencies> {
+        let file = File::open(path)?;
+        Frequencies::from_reader(BufReader::new(file))
+    }
+
+    // place in the list, case-insensitive. None for words it doesn't have
+    pub fn rank(&self, word: &str) -> Option<usize> {
+        self.ranks.get(&word.to_uppercase()).cloned()
+    }
+
+    pub fn len(&self) -> usize {
+        self.ranks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranks.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranks_follow_the_list() {
+        let list = b"the 5000\nCat 120\n\ndog\ncat 3\n\xff\n";
+        let frequencies = Frequencies::from_reader(&list[..]).unwrap();
+        assert_eq!(frequencies.len(), 3);
+        assert_eq!(frequencies.rank("THE"), Some(0));
+        assert_eq!(frequencies.rank("cat"), Some(1));
+        assert_eq!(frequencies.rank("Dog"), Some(2));
+        assert_eq!(frequencies.rank("quiz"), None);
+    }
+}
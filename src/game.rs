@@ -1,23 +1,102 @@
-use board::{Board, Piece};
+use board::Board;
+use dictionary::Dictionary;
+use rejection::{self, Rejection};
+use rules::Rules;
+use solver;
+use suggest;
 use std::cmp::{Ordering, PartialEq};
 use std::fmt::{self, Formatter, Display};
-
-// used in tests only
-#[allow(unused_imports)]
-use board;
+use std::sync::Arc;
 
 pub struct Game {
     pub board: Board,
     pub player: Player,
     pub guesses: Guesses,
+    // a dictionary is not required to play the game,
+    // without one every word on the board is accepted
+    // shared so the same dictionary can be reused across games
+    pub dictionary: Option<Arc<Dictionary>>,
+    pub rules: Rules,
+    // every dictionary word on the board that's long enough to count,
+    // worked out the first time it's needed, with the minimum length used
+    solutions: Option<(usize, Vec<String>)>,
 }
 
 impl Game {
-    pub fn add_guess(&mut self, word: String) {
-        self.guesses.add_guess(word, &self.board)
+    pub fn new(board: Board, player: Player, dictionary: Option<Arc<Dictionary>>) -> Game {
+        Game {
+            board,
+            player,
+            guesses: Guesses::new(),
+            dictionary,
+            rules: Rules::default(),
+            solutions: None,
+        }
+    }
+
+    // points the word earned, or why it didn't count
+    pub fn add_guess(&mut self, word: String) -> Result<usize, Rejection> {
+        self.add(Guess::new(word))
+    }
+
+    // like `add_guess`, noting that it came `secs` seconds into the round
+    pub fn add_guess_at(&mut self, word: String, secs: u64) -> Result<usize, Rejection> {
+        self.add(Guess::new(word).at(secs))
+    }
+
+    fn add(&mut self, guess: Guess) -> Result<usize, Rejection> {
+        let word = guess.word.clone();
+        let min = self.rules.min_word_length;
+        if word.chars().count() < min {
+            let rejection = Rejection::TooShort { min };
+            let suggestion = self.suggestion_for(&word);
+            self.guesses.reject(guess, rejection.clone());
+            if let Some(suggestion) = suggestion {
+                self.guesses.suggest(suggestion);
+            }
+            return Err(rejection);
+        }
+        let result = self.guesses
+            .add(guess, &self.board, self.dictionary.as_deref());
+        // a repeat is already on the list, anything else may be a typo
+        match result {
+            Err(Rejection::Duplicate) | Ok(_) => (),
+            Err(_) => {
+                if let Some(suggestion) = self.suggestion_for(&word) {
+                    self.guesses.suggest(suggestion);
+                }
+            }
+        }
+        result
+    }
+
+    // the closest word on the board the player hasn't found yet,
+    // lowercase. None without a dictionary or when nothing is close
+    pub fn suggestion_for(&mut self, word: &str) -> Option<String> {
+        let found: Vec<String> = self.guesses
+            .valid()
+            .iter()
+            .map(|g| g.word().to_uppercase())
+            .collect();
+        let solutions = self.solutions()?;
+        suggest::closest(word, solutions, &found).map(|w| w.to_lowercase())
+    }
+
+    // all words that can be found on this board and count under the rules,
+    // uppercase and sorted. None when there is no dictionary to solve the board with
+    pub fn solutions(&mut self) -> Option<&[String]> {
+        let min = self.rules.min_word_length;
+        if self.solutions.as_ref().map(|&(m, _)| m) != Some(min) {
+            let dictionary = self.dictionary.as_ref()?;
+            let mut words = solver::solve(&self.board, dictionary);
+            words.retain(|w| w.chars().count() >= min);
+            self.solutions = Some((min, words));
+        }
+        self.solutions.as_ref().map(|(_, words)| words.as_slice())
     }
 }
 
+#[derive(Debug, Clone)]
 pub struct Player {
     pub name: String,
 }
@@ -32,16 +111,58 @@ impl Player {
 pub struct Guess {
     word: String,
     score: usize,
+    // set once the guess has been turned down
+    rejection: Option<Rejection>,
+    // a word on the board the player may have meant instead
+    suggestion: Option<String>,
+    // seconds into the round the guess was made, if anyone was counting
+    at: Option<u64>,
+    // how many guesses came before it, see `Guesses::add`
+    order: usize,
 }
 
 impl Guess {
     pub fn new(word: String) -> Guess {
         Guess {
             score: Guess::calculate_score(word.len()),
-            word: word,
+            word,
+            rejection: None,
+            suggestion: None,
+            at: None,
+            order: 0,
         }
     }
 
+    pub fn at(mut self, secs: u64) -> Guess {
+        self.at = Some(secs);
+        self
+    }
+
+    pub fn at_secs(&self) -> Option<u64> {
+        self.at
+    }
+
+    // guesses go in different lists, this puts them back in the order they were made
+    pub fn order(&self) -> usize {
+        self.order
+    }
+
+    pub fn word(&self) -> &str {
+        &self.word
+    }
+
+    pub fn score(&self) -> usize {
+        self.score
+    }
+
+    pub fn rejection(&self) -> Option<&Rejection> {
+        self.rejection.as_ref()
+    }
+
+    pub fn suggestion(&self) -> Option<&str> {
+        self.suggestion.as_deref()
+    }
+
     fn calculate_score(n: usize) -> usize {
         // 3 is the basic number by which scores are calculated
         let magic_number = 3;
@@ -52,80 +173,23 @@ impl Guess {
         }
     }
 
-    fn is_valid(&self, board: &Board) -> bool {
-        // get vector of vector of &Piece
-        // this should probably be made into its own struct or data type,
-        // but this will suffice for proof of concept
-        let mut collection: Vec<Vec<&Piece>> = vec![];
-
-        // verify letters exist on board
-        // if letter exists, add all instances to collection
-        for letter in self.word.to_uppercase().chars() {
-            match board.first_instance_of(letter) {
-                Some(_x) => {
-                    collection.push(board.get_all_instances_of(letter));
-                    continue;
-                }
-                None => return false,
-            };
+    // can the word be traced on the board, and if not, why not
+    pub fn check(&self, board: &Board) -> Result<(), Rejection> {
+        match rejection::diagnose(board, &self.word) {
+            Some(rejection) => Err(rejection),
+            None => Ok(()),
         }
-
-        is_valid_path(&collection, 0, None, &mut vec![])
     }
-}
-
-// tests all possible paths and returns true when/if one is valid
-// final parameter is used to ensure no duplicate pieces
-fn is_valid_path<'a>(
-    collection: &Vec<Vec<&'a Piece>>,
-    index: usize,
-    prev_piece: Option<&Piece>,
-    path: &mut Vec<&'a Piece>,
-) -> bool {
-    for piece in collection[index].iter() {
-        // if piece is duplicate, not valid
-        if path.contains(piece) {
-            continue;
-        }
-        path.push(piece.clone());
 
-        // not valid if piece is not a neighbor
-        match prev_piece {
-            Some(x) => {
-                if !piece.is_neighbor(x) {
-                    continue;
-                }
-            }
-            None => (),
-        }
-
-        // search subsequent pieces if not at last piece
-        if index < collection.len() - 1 {
-            if is_valid_path(collection, index + 1, Some(piece), path) {
-                return true;
-            } else {
-                // if this piece doesn't have a valid path,
-                // remove piece and continue
-                path.pop();
-                continue;
-            }
-        }
-
-        // if we've made it this far
-        // then we are at the last piece and have a valid path!
-        return true;
+    pub fn is_valid(&self, board: &Board) -> bool {
+        self.check(board).is_ok()
     }
-    false
 }
 
-
+// the same word whatever the case, like the dictionary sees it
 impl PartialEq for Guess {
     fn eq(&self, other: &Guess) -> bool {
-        self.word == other.word
-    }
-
-    fn ne(&self, other: &Guess) -> bool {
-        self.word != other.word
+        self.word.to_uppercase() == other.word.to_uppercase()
     }
 }
 
@@ -138,12 +202,28 @@ impl Display for Guess {
     }
 }
 
-#[derive(Debug)]
+// which list a guess ended up in
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Bucket {
+    Valid,
+    Invalid,
+    NotInDict,
+}
+
+#[derive(Debug, Default)]
 pub struct Guesses {
     invalid: Vec<Guess>,
     valid: Vec<Guess>,
     not_in_dict: Vec<Guess>,
+    // words entered again after they were already found
+    duplicates: Vec<Guess>,
     score: usize,
+    // points taken off for hints
+    penalty: usize,
+    // bucket of every guess in the order they were added, for undo
+    history: Vec<Bucket>,
+    // guesses made so far, repeats and undone ones included
+    made: usize,
 }
 
 impl Guesses {
@@ -152,35 +232,141 @@ impl Guesses {
             invalid: vec![],
             valid: vec![],
             not_in_dict: vec![],
+            duplicates: vec![],
             score: 0,
+            penalty: 0,
+            history: vec![],
+            made: 0,
         }
     }
 
-    pub fn add_guess(&mut self, word: String, board: &Board) {
-        let guess = Guess::new(word);
-        // skip if duplicate word
-        if self.valid.contains(&guess) {
-            return;
-        }
-        // add to proper queue based on validity
-        if guess.is_valid(board) {
+    pub fn valid(&self) -> &[Guess] {
+        &self.valid
+    }
+
+    pub fn invalid(&self) -> &[Guess] {
+        &self.invalid
+    }
+
+    pub fn not_in_dict(&self) -> &[Guess] {
+        &self.not_in_dict
+    }
+
+    pub fn duplicates(&self) -> &[Guess] {
+        &self.duplicates
+    }
+
+    // points for valid words less any penalties, never below zero
+    pub fn score(&self) -> usize {
+        self.score.saturating_sub(self.penalty)
+    }
+
+    pub fn penalty(&self) -> usize {
+        self.penalty
+    }
+
+    pub fn add_penalty(&mut self, points: usize) {
+        self.penalty += points;
+    }
+
+    // points the word earned, or why it didn't count
+    pub fn add_guess(
+        &mut self,
+        word: String,
+        board: &Board,
+        dictionary: Option<&Dictionary>,
+    ) -> Result<usize, Rejection> {
+        self.add(Guess::new(word), board, dictionary)
+    }
+
+    pub fn add(
+        &mut self,
+        mut guess: Guess,
+        board: &Board,
+        dictionary: Option<&Dictionary>,
+    ) -> Result<usize, Rejection> {
+        let rejection = if self.valid.contains(&guess) {
+            Rejection::Duplicate
+        } else if let Err(rejection) = guess.check(board) {
+            rejection
+        } else {
             // if dictionary exists, check for existence
             // if no dictionary, then word is valid by default
-            // TODO?: process this in a thread?
-            let word_upper = guess.word.to_uppercase();
-            match &board.dictionary {
-                &Some(ref dict) => {
-                    if dict.contains(&word_upper) {
-                        self.valid.push(guess)
-                    } else {
-                        self.not_in_dict.push(guess)
-                    }
-                }
-                &None => self.valid.push(guess),
+            let in_dict = match dictionary {
+                Some(dict) => dict.contains(&guess.word),
+                None => true,
+            };
+            if !in_dict {
+                Rejection::NotInDictionary
+            } else {
+                let points = guess.score;
+                self.score += points;
+                self.number(&mut guess);
+                self.valid.push(guess);
+                self.history.push(Bucket::Valid);
+                return Ok(points);
             }
-        } else {
-            self.invalid.push(guess)
+        };
+        self.reject(guess, rejection.clone());
+        Err(rejection)
+    }
+
+    // file a guess that didn't count along with the reason.
+    // repeats changed nothing, so there is nothing to undo for them
+    pub fn reject(&mut self, mut guess: Guess, rejection: Rejection) {
+        let bucket = match rejection {
+            Rejection::Duplicate => None,
+            Rejection::NotInDictionary => Some(Bucket::NotInDict),
+            _ => Some(Bucket::Invalid),
+        };
+        guess.rejection = Some(rejection);
+        self.number(&mut guess);
+        match bucket {
+            None => self.duplicates.push(guess),
+            Some(Bucket::NotInDict) => self.not_in_dict.push(guess),
+            Some(_) => self.invalid.push(guess),
         }
+        self.history.extend(bucket);
+    }
+
+    fn number(&mut self, guess: &mut Guess) {
+        guess.order = self.made;
+        self.made += 1;
+    }
+
+    // the most recent guess that went on a list, repeats aside
+    pub fn last(&self) -> Option<&Guess> {
+        match *self.history.last()? {
+            Bucket::Valid => self.valid.last(),
+            Bucket::Invalid => self.invalid.last(),
+            Bucket::NotInDict => self.not_in_dict.last(),
+        }
+    }
+
+    // attach a "did you mean" to the most recent guess that didn't count
+    pub fn suggest(&mut self, word: String) {
+        let guess = match self.history.last() {
+            Some(&Bucket::Invalid) => self.invalid.last_mut(),
+            Some(&Bucket::NotInDict) => self.not_in_dict.last_mut(),
+            _ => None,
+        };
+        if let Some(guess) = guess {
+            guess.suggestion = Some(word);
+        }
+    }
+
+    // take back the most recent guess, returning it and where it had gone
+    pub fn undo(&mut self) -> Option<(Guess, Bucket)> {
+        let bucket = self.history.pop()?;
+        let guess = match bucket {
+            Bucket::Valid => self.valid.pop(),
+            Bucket::Invalid => self.invalid.pop(),
+            Bucket::NotInDict => self.not_in_dict.pop(),
+        }?;
+        if bucket == Bucket::Valid {
+            self.score -= guess.score;
+        }
+        Some((guess, bucket))
     }
 }
 
@@ -189,31 +375,51 @@ impl Display for Guesses {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         // `write!` is like `format!`, but it will write the formatted string
         // into a buffer (the first argument)
-        write!(f, "\nValid words\n=============\n")?;
+        writeln!(f, "\nValid words\n=============")?;
         for guess in self.valid.iter() {
-            write!(f, "{}\n", guess)?;
+            writeln!(f, "{}", guess)?;
         }
 
-        write!(f, "\nInvalid words\n=============\n")?;
+        writeln!(f, "\nInvalid words\n=============")?;
         for guess in self.invalid.iter() {
-            write!(f, "{}\n", guess)?;
+            write_rejected(f, guess, true)?;
         }
 
-        write!(f, "\nNot in dictionary\n=============\n")?;
+        writeln!(f, "\nNot in dictionary\n=============")?;
         for guess in self.not_in_dict.iter() {
-            write!(f, "{}\n", guess)?;
+            // the heading already says why
+            write_rejected(f, guess, false)?;
         }
 
-        // it appears that type annotations for sum are quite common, though not ideal
-        // https://users.rust-lang.org/t/using-iterator-sum-in-an-expression/7126/5
-        write!(
-            f,
-            "\nTotal Score: {:?}",
-            self.valid.iter().map(|ref x| x.score).sum::<usize>()
-        )
+        if !self.duplicates.is_empty() {
+            writeln!(f, "\nRepeated words\n=============")?;
+            for guess in self.duplicates.iter() {
+                writeln!(f, "{}", guess)?;
+            }
+        }
+
+        if self.penalty > 0 {
+            write!(f, "\nHint penalty: -{}", self.penalty)?;
+        }
+        write!(f, "\nTotal Score: {:?}", self.score())
     }
 }
 
+// a guess that didn't count, with why and what it might have been
+fn write_rejected(f: &mut Formatter, guess: &Guess, with_reason: bool) -> fmt::Result {
+    let mut notes = vec![];
+    if let (true, Some(rejection)) = (with_reason, guess.rejection.as_ref()) {
+        notes.push(rejection.to_string());
+    }
+    if let Some(ref suggestion) = guess.suggestion {
+        notes.push(format!("did you mean \"{}\"?", suggestion));
+    }
+    if notes.is_empty() {
+        writeln!(f, "{}", guess)
+    } else {
+        writeln!(f, "{} ({})", guess, notes.join("; "))
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -222,130 +428,173 @@ mod tests {
     #[test]
     fn guesses_add_guess_no_duplicates() {
         let mut my_guesses = Guesses::new();
-        let my_board = Board::from(&vec!['T', 'E', 'S', 'T', 'R']);
+        let letters: Vec<String> = "TESTRABCDEFGHIJK".chars().map(|c| c.to_string()).collect();
+        let my_board = Board::try_from_letters(4, 4, &letters).unwrap();
         let my_string = String::from("test");
-        my_guesses.add_guess(my_string, &my_board);
+        let _ = my_guesses.add_guess(my_string, &my_board, None);
         let my_string = String::from("test");
-        my_guesses.add_guess(my_string, &my_board);
+        let _ = my_guesses.add_guess(my_string, &my_board, None);
         assert_eq!(my_guesses.invalid.len(), 0, "invalid is wrong length");
         assert_eq!(my_guesses.valid.len(), 1, "valid is wrong length");
+        assert_eq!(my_guesses.score, 1, "duplicate was scored twice");
+        assert_eq!(my_guesses.duplicates.len(), 1);
     }
 
     #[test]
-    fn guess_calculate_score() {
-        let score = Guess::calculate_score(0);
-        assert_eq!(score, 0);
-        let score = Guess::calculate_score(2);
-        assert_eq!(score, 0);
-        let score = Guess::calculate_score(3);
-        assert_eq!(score, 1);
-        let score = Guess::calculate_score(4);
-        assert_eq!(score, 1);
-        let score = Guess::calculate_score(10);
-        assert_eq!(score, 7);
+    fn a_word_in_another_case_is_a_repeat() {
+        let mut my_guesses = Guesses::new();
+        let letters: Vec<String> = "CATSRABCDEFGHIJK".chars().map(|c| c.to_string()).collect();
+        let my_board = Board::try_from_letters(4, 4, &letters).unwrap();
+        let dict = Dictionary::from_words(vec!["cat"]);
+        assert_eq!(my_guesses.add_guess(String::from("cat"), &my_board, Some(&dict)), Ok(1));
+        for word in &["CAT", "Cat"] {
+            assert_eq!(
+                my_guesses.add_guess(word.to_string(), &my_board, Some(&dict)),
+                Err(Rejection::Duplicate)
+            );
+        }
+        assert_eq!(my_guesses.valid().len(), 1);
+        assert_eq!(my_guesses.duplicates().len(), 2);
+        assert_eq!(my_guesses.score(), 1);
     }
 
     #[test]
-    fn guess_is_valid_letter_not_in_board() {
-        let board = Board::from(&vec!['A', 'B', 'C', 'D', 'E']);
-        let guess = Guess::new(String::from("testr"));
-        assert_eq!(guess.is_valid(&board), false);
+    fn guesses_add_guess_says_why() {
+        let mut my_guesses = Guesses::new();
+        let letters: Vec<String> = "TESTRABCDEFGHIJK".chars().map(|c| c.to_string()).collect();
+        let my_board = Board::try_from_letters(4, 4, &letters).unwrap();
+        let dict = Dictionary::from_words(vec!["test"]);
+        assert_eq!(my_guesses.add_guess(String::from("test"), &my_board, Some(&dict)), Ok(1));
+        assert_eq!(
+            my_guesses.add_guess(String::from("test"), &my_board, Some(&dict)),
+            Err(Rejection::Duplicate)
+        );
+        assert_eq!(
+            my_guesses.add_guess(String::from("set"), &my_board, Some(&dict)),
+            Err(Rejection::NotInDictionary)
+        );
+        assert_eq!(
+            my_guesses.add_guess(String::from("zebra"), &my_board, Some(&dict)),
+            Err(Rejection::LetterNotOnBoard { letter: 'Z' })
+        );
+        assert_eq!(
+            my_guesses.invalid[0].rejection(),
+            Some(&Rejection::LetterNotOnBoard { letter: 'Z' })
+        );
+        let summary = my_guesses.to_string();
+        assert!(summary.contains("zebra (the letter Z is not on the board)"));
+        assert!(summary.contains("Repeated words\n=============\ntest\n"));
     }
 
     #[test]
-    fn is_valid_path_single_element_true() {
-        // trivial case, but when collection.len() == 1, a valid path always exists
-        let piece = Piece::new('A', 0);
-        let collection = vec![vec![&piece]];
+    fn game_add_guess_checks_length() {
+        let letters: Vec<String> = "TESTRABCDEFGHIJK".chars().map(|c| c.to_string()).collect();
+        let board = Board::try_from_letters(4, 4, &letters).unwrap();
+        let mut game = Game::new(board, Player::new(String::from("p")), None);
+        game.rules.min_word_length = 4;
         assert_eq!(
-            is_valid_path(&collection, 0, None, &mut vec![]),
-            true,
-            "collection with 1 piece does not have valid path"
+            game.add_guess(String::from("set")),
+            Err(Rejection::TooShort { min: 4 })
         );
+        assert_eq!(game.add_guess(String::from("test")), Ok(1));
+        assert_eq!(game.guesses.invalid().len(), 1);
     }
 
     #[test]
-    fn is_valid_path_non_adjacent_pieces() {
-        let piece1 = Piece::new('A', 0);
-        let piece2 = Piece::new('A', 2);
-        let collection = vec![vec![&piece1], vec![&piece2]];
-        assert_eq!(
-            is_valid_path(&collection, 0, None, &mut vec![]),
-            false,
-            "collection with non-adjacent pieces has valid path"
-        );
+    fn game_add_guess_suggests_a_word() {
+        let letters: Vec<String> = "TESTRABCDEFGHIJK".chars().map(|c| c.to_string()).collect();
+        let board = Board::try_from_letters(4, 4, &letters).unwrap();
+        let dict = Dictionary::from_words(vec!["test", "tests", "set"]);
+        let mut game = Game::new(board, Player::new(String::from("p")), Some(Arc::new(dict)));
+        assert!(game.add_guess(String::from("tezt")).is_err());
+        assert!(game.add_guess(String::from("rest")).is_err());
+        assert_eq!(game.guesses.invalid()[0].suggestion(), Some("test"));
+        assert_eq!(game.guesses.not_in_dict()[0].suggestion(), Some("test"));
+        assert!(game
+            .guesses
+            .to_string()
+            .contains("rest (did you mean \"test\"?)\n"));
     }
 
     #[test]
-    fn is_valid_path_adjacent_pieces() {
-        let piece1 = Piece::new('A', 0);
-        let piece2 = Piece::new('A', 1);
-        let collection = vec![vec![&piece1], vec![&piece2]];
-        assert_eq!(
-            is_valid_path(&collection, 0, None, &mut vec![]),
-            true,
-            "collection with adjacent pieces does not have valid path"
-        );
+    fn suggestions_are_long_enough_to_count() {
+        let letters: Vec<String> = "TESTRABCDEFGHIJK".chars().map(|c| c.to_string()).collect();
+        let board = Board::try_from_letters(4, 4, &letters).unwrap();
+        let dict = Dictionary::from_words(vec!["set", "test"]);
+        let mut game = Game::new(board, Player::new(String::from("p")), Some(Arc::new(dict)));
+        assert_eq!(game.suggestion_for("sez"), Some(String::from("set")));
+        game.rules.min_word_length = 4;
+        assert_eq!(game.solutions(), Some(&[String::from("TEST")][..]));
+        assert_eq!(game.suggestion_for("sez"), None);
+        assert_eq!(game.suggestion_for("tezt"), Some(String::from("test")));
     }
 
     #[test]
-    fn is_valid_path_same_pieces() {
-        let piece1 = Piece::new('A', 0);
-        let piece2 = Piece::new('A', 1);
-        let collection = vec![
-            vec![&piece1],
-            vec![&piece2],
-            vec![&piece1] // should not be allowed to use same piece twice
-        ];
-        assert_eq!(
-            is_valid_path(&collection, 0, None, &mut vec![]),
-            false,
-            "collection with duplicate pieces has valid path"
-        );
+    fn guesses_undo_most_recent_first() {
+        let mut my_guesses = Guesses::new();
+        let letters: Vec<String> = "TESTRABCDEFGHIJK".chars().map(|c| c.to_string()).collect();
+        let my_board = Board::try_from_letters(4, 4, &letters).unwrap();
+        let _ = my_guesses.add_guess(String::from("test"), &my_board, None);
+        let _ = my_guesses.add_guess(String::from("zebra"), &my_board, None);
+        let _ = my_guesses.add_guess(String::from("test"), &my_board, None);
+
+        let (guess, bucket) = my_guesses.undo().unwrap();
+        assert_eq!(guess.word(), "zebra");
+        assert_eq!(bucket, Bucket::Invalid);
+        assert_eq!(my_guesses.score, 1);
+
+        let (guess, bucket) = my_guesses.undo().unwrap();
+        assert_eq!(guess.word(), "test");
+        assert_eq!(bucket, Bucket::Valid);
+        assert_eq!(my_guesses.score, 0);
+        assert!(my_guesses.valid.is_empty());
+
+        assert!(my_guesses.undo().is_none());
     }
 
     #[test]
-    fn is_valid_path_last_piece_wrong() {
-        let piece1 = Piece::new('A', 0);
-        let piece2 = Piece::new('A', 1);
-        let piece3 = Piece::new('A', 20);
-        let collection = vec![vec![&piece1], vec![&piece2], vec![&piece3]];
-        assert_eq!(
-            is_valid_path(&collection, 0, None, &mut vec![]),
-            false,
-            "collection with only partial path has full valid path"
-        );
+    fn guesses_penalty_comes_off_the_score() {
+        let mut my_guesses = Guesses::new();
+        let letters: Vec<String> = "TESTRABCDEFGHIJK".chars().map(|c| c.to_string()).collect();
+        let my_board = Board::try_from_letters(4, 4, &letters).unwrap();
+        let _ = my_guesses.add_guess(String::from("test"), &my_board, None);
+        my_guesses.add_penalty(3);
+        assert_eq!(my_guesses.score(), 0, "score went below zero");
+        assert!(my_guesses.to_string().ends_with("\nHint penalty: -3\nTotal Score: 0"));
     }
 
-    // cargo test is_valid_path_try_all_combinations -- --nocapture
     #[test]
-    fn is_valid_path_try_all_combinations() {
-        // should be able to try all possible combinations if collection has lots of invalid paths
-        // yes, this test is quite ugly. I was trying to prove a point and it got out of hand
-        let valid0 = Piece::new('A', 0);
-        let valid5 = Piece::new('B', board::BOARD_DIMENSIONS);
-        let valid6 = Piece::new('C', board::BOARD_DIMENSIONS * 2);
-        let valid11 = Piece::new('D', board::BOARD_DIMENSIONS * 3);
-
-        let invalid3 = Piece::new('E', board::BOARD_DIMENSIONS - 1);
-        let invalid4 = Piece::new('F', board::BOARD_DIMENSIONS * 2 - 1);
-        let invalid8 = Piece::new('G', board::BOARD_DIMENSIONS * 3 - 1);
-        let invalid9 = Piece::new('H', board::BOARD_DIMENSIONS - 1);
-        let invalid13 = Piece::new('I', board::BOARD_DIMENSIONS - 2);
-        let invalid14 = Piece::new('J', board::BOARD_DIMENSIONS * 2 - 2);
-        let invalid24 = Piece::new('K', board::BOARD_DIMENSIONS * 3 - 2);
-        let invalid25 = Piece::new('L', board::BOARD_DIMENSIONS - 2);
-
-        let collection = vec![
-            vec![&invalid3, &invalid4, &valid0],
-            vec![&invalid8, &invalid9, &valid5],
-            vec![&invalid13, &invalid14, &valid6],
-            vec![&invalid24, &invalid25, &valid11],
-        ];
-        assert_eq!(
-            is_valid_path(&collection, 0, None, &mut vec![]),
-            true,
-            "collection with complicated path is valid"
-        );
+    fn guesses_add_guess_checks_dictionary() {
+        let mut my_guesses = Guesses::new();
+        let letters: Vec<String> = "TESTRABCDEFGHIJK".chars().map(|c| c.to_string()).collect();
+        let my_board = Board::try_from_letters(4, 4, &letters).unwrap();
+        let dict = Dictionary::from_words(vec!["test"]);
+        let _ = my_guesses.add_guess(String::from("test"), &my_board, Some(&dict));
+        let _ = my_guesses.add_guess(String::from("set"), &my_board, Some(&dict));
+        assert_eq!(my_guesses.valid.len(), 1, "valid is wrong length");
+        assert_eq!(my_guesses.not_in_dict.len(), 1, "not_in_dict is wrong length");
+        assert_eq!(my_guesses.score, 1);
+    }
+
+    #[test]
+    fn guess_calculate_score() {
+        let score = Guess::calculate_score(0);
+        assert_eq!(score, 0);
+        let score = Guess::calculate_score(2);
+        assert_eq!(score, 0);
+        let score = Guess::calculate_score(3);
+        assert_eq!(score, 1);
+        let score = Guess::calculate_score(4);
+        assert_eq!(score, 1);
+        let score = Guess::calculate_score(10);
+        assert_eq!(score, 7);
+    }
+
+    #[test]
+    fn guess_is_valid_letter_not_in_board() {
+        let letters: Vec<String> = "ABCDEFGHIJKLMNOP".chars().map(|c| c.to_string()).collect();
+        let board = Board::try_from_letters(4, 4, &letters).unwrap();
+        let guess = Guess::new(String::from("testr"));
+        assert!(!guess.is_valid(&board));
     }
 }
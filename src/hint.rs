@@ -0,0 +1,177 @@
+//! Hints about words the player hasn't found yet, worked out from every
+//! word the solver found on the board
+
+use std::fmt::{self, Display, Formatter};
+
+use rand::Rng;
+
+use board::Board;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HintKind {
+    // how many words are left, by length
+    Lengths,
+    // the first two letters of a word
+    Letters,
+    // a tile that starts a word
+    Tile,
+    // the length of the longest word
+    Longest,
+}
+
+pub const HINT_KINDS: [HintKind; 4] = [
+    HintKind::Lengths,
+    HintKind::Letters,
+    HintKind::Tile,
+    HintKind::Longest,
+];
+
+impl HintKind {
+    pub fn from_name(name: &str) -> Option<HintKind> {
+        match name {
+            "lengths" => Some(HintKind::Lengths),
+            "letters" => Some(HintKind::Letters),
+            "tile" => Some(HintKind::Tile),
+            "longest" => Some(HintKind::Longest),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Hint {
+    // (length, number of words) for every length with words left, shortest first
+    Lengths(Vec<(usize, usize)>),
+    Letters(String),
+    // (row, col) of the tile
+    Tile(usize, usize),
+    Longest(usize),
+    // every word has been found
+    NothingLeft,
+}
+
+// a hint of the given kind about one of `solutions` that isn't in `found`.
+// `found` must be uppercase, like the solutions
+pub fn hint<R: Rng>(
+    kind: HintKind,
+    board: &Board,
+    solutions: &[String],
+    found: &[String],
+    rng: &mut R,
+) -> Hint {
+    let left: Vec<&String> = solutions.iter().filter(|w| !found.contains(w)).collect();
+    if left.is_empty() {
+        return Hint::NothingLeft;
+    }
+    match kind {
+        HintKind::Lengths => {
+            let mut lengths: Vec<(usize, usize)> = vec![];
+            for word in left.iter() {
+                let len = word.chars().count();
+                match lengths.iter().position(|&(l, _)| l == len) {
+                    Some(i) => lengths[i].1 += 1,
+                    None => lengths.push((len, 1)),
+                }
+            }
+            lengths.sort();
+            Hint::Lengths(lengths)
+        }
+        HintKind::Letters => {
+            let word = left[rng.gen_range(0, left.len())];
+            Hint::Letters(word.chars().take(2).collect())
+        }
+        HintKind::Tile => {
+            let word = left[rng.gen_range(0, left.len())];
+            match board.find_paths(word).first().and_then(|path| path.first()) {
+                Some(&(row, col)) => Hint::Tile(row, col),
+                // solutions always have a path, but just in case
+                None => Hint::Letters(word.chars().take(2).collect()),
+            }
+        }
+        HintKind::Longest => {
+            let longest = left.iter().map(|w| w.chars().count()).max().unwrap_or(0);
+            Hint::Longest(longest)
+        }
+    }
+}
+
+impl Display for Hint {
+    // tiles are shown by highlighting the board, so they only get a caption here
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match *self {
+            Hint::Lengths(ref lengths) => {
+                let counts: Vec<String> = lengths
+                    .iter()
+                    .map(|&(len, count)| format!("{} with {} letters", count, len))
+                    .collect();
+                write!(f, "Words left to find: {}", counts.join(", "))
+            }
+            Hint::Letters(ref letters) => write!(f, "There is a word starting with {}", letters),
+            Hint::Tile(..) => write!(f, "There is a word starting on the marked tile"),
+            Hint::Longest(len) => write!(f, "There is a {}-letter word here", len),
+            Hint::NothingLeft => write!(f, "You found every word, there's nothing left to hint at!"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::thread_rng;
+
+    fn words(list: &[&str]) -> Vec<String> {
+        list.iter().map(|w| w.to_string()).collect()
+    }
+
+    #[test]
+    fn lengths_only_count_words_left() {
+        let solutions = words(&["CAT", "CATS", "DOG", "GO", "TAC"]);
+        let found = words(&["CAT"]);
+        let board = Board::cats_and_dog();
+        let hint = hint(HintKind::Lengths, &board, &solutions, &found, &mut thread_rng());
+        assert_eq!(hint, Hint::Lengths(vec![(2, 1), (3, 2), (4, 1)]));
+        assert_eq!(
+            hint.to_string(),
+            "Words left to find: 1 with 2 letters, 2 with 3 letters, 1 with 4 letters"
+        );
+    }
+
+    #[test]
+    fn letters_and_tile_point_at_a_word_left() {
+        let solutions = words(&["CAT", "DOG"]);
+        let found = words(&["CAT"]);
+        let board = Board::cats_and_dog();
+        for _ in 0..10 {
+            let letters = hint(HintKind::Letters, &board, &solutions, &found, &mut thread_rng());
+            assert_eq!(letters, Hint::Letters(String::from("DO")));
+            let tile = hint(HintKind::Tile, &board, &solutions, &found, &mut thread_rng());
+            assert_eq!(tile, Hint::Tile(2, 0));
+        }
+    }
+
+    #[test]
+    fn longest_word_left() {
+        let solutions = words(&["CAT", "CATS", "DOG"]);
+        let found = words(&["CATS"]);
+        let board = Board::cats_and_dog();
+        let hint = hint(HintKind::Longest, &board, &solutions, &found, &mut thread_rng());
+        assert_eq!(hint.to_string(), "There is a 3-letter word here");
+    }
+
+    #[test]
+    fn nothing_left() {
+        let solutions = words(&["CAT"]);
+        let board = Board::cats_and_dog();
+        let hint = hint(HintKind::Tile, &board, &solutions, &solutions, &mut thread_rng());
+        assert_eq!(hint, Hint::NothingLeft);
+    }
+
+    #[test]
+    fn kinds_by_name() {
+        for kind in HINT_KINDS.iter() {
+            let name = format!("{:?}", kind).to_lowercase();
+            assert_eq!(HintKind::from_name(&name), Some(*kind));
+        }
+        assert_eq!(HintKind::from_name("answer"), None);
+    }
+}
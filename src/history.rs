@@ -0,0 +1,428 @@
+//! Every finished game, appended to a history file one JSON object per line,
+//! and the statistics worked out from them.
+//! The file lives in `$XDG_DATA_HOME/boggle/`, or `~/.local/share/boggle/`
+
+use std::env;
+use std::fmt::{self, Display, Formatter};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde_json;
+
+use game::{Game, Guess};
+use rejection::Rejection;
+use rules::Rules;
+
+// how many of the latest games count as "recent" for trends
+const RECENT_GAMES: usize = 5;
+
+// one finished game
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GameRecord {
+    // unix time, in seconds, when the round ended
+    pub played_at: u64,
+    pub player: String,
+    // see `Board::code`
+    pub board: String,
+    // None for boards that weren't dealt at random
+    pub seed: Option<u64>,
+    pub rules: Rules,
+    // the time limit, and how long the round actually lasted
+    pub minutes: f64,
+    pub seconds_played: u64,
+    // in the order they were made
+    pub guesses: Vec<GuessRecord>,
+    pub penalty: usize,
+    pub score: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GuessRecord {
+    pub word: String,
+    pub points: usize,
+    // None when the word counted
+    pub rejection: Option<Rejection>,
+    // seconds into the round
+    pub at_secs: Option<u64>,
+}
+
+impl GuessRecord {
+    fn new(guess: &Guess, counted: bool) -> GuessRecord {
+        GuessRecord {
+            word: guess.word().to_string(),
+            points: if counted { guess.score() } else { 0 },
+            rejection: guess.rejection().cloned(),
+            at_secs: guess.at_secs(),
+        }
+    }
+}
+
+impl GameRecord {
+    pub fn new(game: &Game, minutes: f64, seconds_played: u64, played_at: u64) -> GameRecord {
+        let guesses = &game.guesses;
+        let mut made: Vec<(&Guess, bool)> = guesses.valid().iter().map(|g| (g, true)).collect();
+        let rejected = guesses
+            .invalid()
+            .iter()
+            .chain(guesses.not_in_dict())
+            .chain(guesses.duplicates());
+        made.extend(rejected.map(|g| (g, false)));
+        made.sort_by_key(|&(g, _)| g.order());
+        let records = made
+            .into_iter()
+            .map(|(g, counted)| GuessRecord::new(g, counted))
+            .collect();
+
+        GameRecord {
+            played_at,
+            player: game.player.name.clone(),
+            board: game.board.code(),
+            seed: game.board.seed(),
+            rules: game.rules.clone(),
+            minutes,
+            seconds_played,
+            guesses: records,
+            penalty: guesses.penalty(),
+            score: guesses.score(),
+        }
+    }
+
+    // "4x4" and so on, taken from the board code
+    pub fn board_size(&self) -> &str {
+        self.board.split(':').next().unwrap_or("")
+    }
+}
+
+// unix time in seconds, as kept in `played_at`
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+// where boggle keeps its files, None if there's no home to put them in
+pub fn data_dir() -> Option<PathBuf> {
+    let base = match env::var_os("XDG_DATA_HOME") {
+        Some(ref dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => {
+            let home = env::var_os("HOME").filter(|h| !h.is_empty())?;
+            Path::new(&home).join(".local").join("share")
+        }
+    };
+    Some(base.join("boggle"))
+}
+
+// write `path` with `write`, into a file next to it that's then moved over it,
+// so a crash or a failed write never leaves half a file behind.
+// the directory it goes in is made if it isn't there
+pub fn write_atomically<P, F>(path: P, write: F) -> io::Result<()>
+where
+    P: AsRef<Path>,
+    F: FnOnce(&mut BufWriter<File>) -> io::Result<()>,
+{
+    let path = path.as_ref();
+    if let Some(dir) = path.parent() {
+        if !dir.as_os_str().is_empty() {
+            fs::create_dir_all(dir)?;
+        }
+    }
+    let mut tmp = path.to_path_buf().into_os_string();
+    tmp.push(".tmp");
+    {
+        let mut file = BufWriter::new(File::create(&tmp)?);
+        write(&mut file)?;
+        file.flush()?;
+    }
+    fs::rename(&tmp, path)
+}
+
+pub struct History {
+    path: PathBuf,
+}
+
+impl History {
+    pub fn new<P: Into<PathBuf>>(path: P) -> History {
+        History { path: path.into() }
+    }
+
+    // history.jsonl in the data directory
+    pub fn open_default() -> Option<History> {
+        data_dir().map(|dir| History::new(dir.join("history.jsonl")))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn append(&self, record: &GameRecord) -> io::Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut line = serde_json::to_string(record).map_err(io::Error::other)?;
+        line.push('\n');
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        // one write per game, so a crash can't leave half a record behind another
+        file.write_all(line.as_bytes())
+    }
+
+    // every game so far, oldest first. no file yet means no games,
+    // and lines that can't be read (e.g. cut short by a crash) are skipped
+    pub fn load(&self) -> io::Result<Vec<GameRecord>> {
+        let file = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(e) => return Err(e),
+        };
+        let mut records = vec![];
+        for line in BufReader::new(file).lines() {
+            if let Ok(record) = serde_json::from_str(&line?) {
+                records.push(record);
+            }
+        }
+        Ok(records)
+    }
+}
+
+// lifetime numbers over a list of games
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stats {
+    pub games: usize,
+    pub average: f64,
+    pub best: usize,
+    pub longest_word: String,
+    // one entry per board size played, smallest board first
+    pub sizes: Vec<SizeStats>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SizeStats {
+    pub size: String,
+    pub games: usize,
+    pub average: f64,
+    pub best: usize,
+    // average over the latest few games on this size
+    pub recent_average: f64,
+}
+
+impl SizeStats {
+    // how the latest games compare with the lot
+    pub fn trend(&self) -> f64 {
+        self.recent_average - self.average
+    }
+}
+
+fn average(scores: &[usize]) -> f64 {
+    if scores.is_empty() {
+        return 0.0;
+    }
+    scores.iter().sum::<usize>() as f64 / scores.len() as f64
+}
+
+impl Stats {
+    // `records` oldest first, as `History::load` returns them
+    pub fn from_records(records: &[GameRecord]) -> Stats {
+        let scores: Vec<usize> = records.iter().map(|r| r.score).collect();
+        // first longest word wins a tie
+        let mut longest_word = "";
+        for record in records.iter() {
+            for guess in record.guesses.iter().filter(|g| g.rejection.is_none()) {
+                if guess.word.chars().count() > longest_word.chars().count() {
+                    longest_word = &guess.word;
+                }
+            }
+        }
+
+        let mut names: Vec<&str> = vec![];
+        for record in records.iter() {
+            if !names.contains(&record.board_size()) {
+                names.push(record.board_size());
+            }
+        }
+        // by area, then rows
+        names.sort_by_key(|name| {
+            let mut dims = name.split('x').map(|n| n.parse::<usize>().unwrap_or(0));
+            let rows = dims.next().unwrap_or(0);
+            let cols = dims.next().unwrap_or(0);
+            (rows * cols, rows)
+        });
+        let sizes = names
+            .into_iter()
+            .map(|name| {
+                let scores: Vec<usize> = records
+                    .iter()
+                    .filter(|r| r.board_size() == name)
+                    .map(|r| r.score)
+                    .collect();
+                let recent = &scores[scores.len().saturating_sub(RECENT_GAMES)..];
+                SizeStats {
+                    size: name.to_string(),
+                    games: scores.len(),
+                    average: average(&scores),
+                    best: scores.iter().cloned().max().unwrap_or(0),
+                    recent_average: average(recent),
+                }
+            })
+            .collect();
+
+        Stats {
+            games: records.len(),
+            average: average(&scores),
+            best: scores.iter().cloned().max().unwrap_or(0),
+            longest_word: longest_word.to_string(),
+            sizes,
+        }
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        writeln!(f, "Your statistics\n===============")?;
+        writeln!(f, "Games played: {}", self.games)?;
+        writeln!(f, "Average score: {:.1}", self.average)?;
+        writeln!(f, "Best score: {}", self.best)?;
+        writeln!(f, "Longest word: {}", self.longest_word)?;
+        if self.sizes.is_empty() {
+            return Ok(());
+        }
+        writeln!(f, "\nBy board size\n=============")?;
+        for size in self.sizes.iter() {
+            writeln!(
+                f,
+                "{}: {} games, average {:.1}, best {}, last {} average {:.1} ({:+.1})",
+                size.size,
+                size.games,
+                size.average,
+                size.best,
+                RECENT_GAMES,
+                size.recent_average,
+                size.trend()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use board::Board;
+    use game::Player;
+
+    fn record(board: &str, score: usize, words: &[&str]) -> GameRecord {
+        GameRecord {
+            played_at: 0,
+            player: String::from("p"),
+            board: board.to_string(),
+            seed: None,
+            rules: Rules::default(),
+            minutes: 3.0,
+            seconds_played: 180,
+            guesses: words
+                .iter()
+                .map(|w| GuessRecord {
+                    word: w.to_string(),
+                    points: 1,
+                    rejection: None,
+                    at_secs: None,
+                })
+                .collect(),
+            penalty: 0,
+            score,
+        }
+    }
+
+    #[test]
+    fn record_keeps_guesses_in_order() {
+        let board = Board::cats_and_dog();
+        let mut game = Game::new(board, Player::new(String::from("eric")), None);
+        let _ = game.add_guess_at(String::from("dog"), 3);
+        let _ = game.add_guess_at(String::from("zebra"), 5);
+        let _ = game.add_guess_at(String::from("dog"), 9);
+        let _ = game.add_guess_at(String::from("cats"), 12);
+        // in the same second, a miss before a hit
+        let _ = game.add_guess_at(String::from("xyz"), 12);
+        let _ = game.add_guess_at(String::from("cat"), 12);
+
+        let record = GameRecord::new(&game, 3.0, 100, 1_700_000_000);
+        assert_eq!(record.board, "4x4:CATSXXXXDOGXXXXX");
+        assert_eq!(record.board_size(), "4x4");
+        assert_eq!(record.score, 3);
+        let words: Vec<(&str, Option<u64>)> = record
+            .guesses
+            .iter()
+            .map(|g| (g.word.as_str(), g.at_secs))
+            .collect();
+        assert_eq!(
+            words,
+            vec![
+                ("dog", Some(3)),
+                ("zebra", Some(5)),
+                ("dog", Some(9)),
+                ("cats", Some(12)),
+                ("xyz", Some(12)),
+                ("cat", Some(12)),
+            ]
+        );
+        assert_eq!(record.guesses[1].rejection, Some(Rejection::LetterNotOnBoard { letter: 'Z' }));
+        assert_eq!(record.guesses[2].rejection, Some(Rejection::Duplicate));
+    }
+
+    #[test]
+    fn atomic_writes_leave_no_trace_of_a_failure() {
+        let dir = env::temp_dir().join(format!("boggle-atomic-{}", std::process::id()));
+        let path = dir.join("new").join("file.txt");
+        write_atomically(&path, |w| w.write_all(b"first")).unwrap();
+        let failed = write_atomically(&path, |w| {
+            w.write_all(b"second")?;
+            Err(io::Error::other("disk full"))
+        });
+        assert!(failed.is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "first");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn history_appends_and_loads() {
+        let path = env::temp_dir().join(format!("boggle-history-{}.jsonl", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let history = History::new(path.clone());
+        assert!(history.load().unwrap().is_empty());
+
+        let first = record("4x4:ABCDEFGHIJKLMNOP", 3, &["ab"]);
+        let second = record("2x2:ABCD", 1, &[]);
+        history.append(&first).unwrap();
+        history.append(&second).unwrap();
+        // a torn line from a crash is skipped
+        OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(b"{\"played_at\":")
+            .unwrap();
+        assert_eq!(history.load().unwrap(), vec![first, second]);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn stats_by_board_size() {
+        let mut records = vec![record("5x5:X", 10, &["quartz"])];
+        for score in 1..8 {
+            records.push(record("4x4:X", score, &["cat"]));
+        }
+        let stats = Stats::from_records(&records);
+        assert_eq!(stats.games, 8);
+        assert_eq!(stats.best, 10);
+        assert_eq!(stats.longest_word, "quartz");
+        assert_eq!(stats.sizes[0].size, "4x4");
+        assert_eq!(stats.sizes[0].average, 4.0);
+        // last five are 3 to 7
+        assert_eq!(stats.sizes[0].recent_average, 5.0);
+        assert_eq!(stats.sizes[1].size, "5x5");
+        assert!(stats
+            .to_string()
+            .contains("4x4: 7 games, average 4.0, best 7, last 5 average 5.0 (+1.0)\n"));
+    }
+}
@@ -0,0 +1,235 @@
+//! Line input that can be interrupted.
+//! Lines are read on a background thread and handed over through a channel,
+//! so a signal handler can wake up a session that is waiting for input
+
+use std::io::{self, BufRead, Read};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError, Sender};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+pub enum InputEvent {
+    Line(String),
+    // ends the current read as if input had closed, but later reads carry on
+    Interrupt,
+    // the input really has closed
+    Eof,
+}
+
+// input that can stop waiting, so a round ends on time
+// even when nothing more is typed
+pub trait TimedInput: BufRead {
+    // wait up to `timeout` for a line, true once there's something to read,
+    // including the end of input
+    fn wait(&mut self, timeout: Duration) -> io::Result<bool>;
+}
+
+pub struct ChannelInput {
+    events: Receiver<InputEvent>,
+    buffer: Vec<u8>,
+    pos: usize,
+    closed: bool,
+    // an interrupt came in while waiting, see `TimedInput::wait`
+    cut_short: bool,
+    // interrupts received since the last line of input
+    interrupts: Arc<AtomicUsize>,
+    // whether the last read was cut short by an interrupt
+    interrupted: Arc<AtomicBool>,
+}
+
+impl ChannelInput {
+    pub fn new() -> (ChannelInput, Sender<InputEvent>) {
+        let (sender, events) = channel();
+        let input = ChannelInput {
+            events,
+            buffer: vec![],
+            pos: 0,
+            closed: false,
+            cut_short: false,
+            interrupts: Arc::new(AtomicUsize::new(0)),
+            interrupted: Arc::new(AtomicBool::new(false)),
+        };
+        (input, sender)
+    }
+
+    // input fed from stdin by a background thread
+    pub fn stdin() -> (ChannelInput, Sender<InputEvent>) {
+        let (input, sender) = ChannelInput::new();
+        let lines = sender.clone();
+        thread::spawn(move || {
+            let stdin = io::stdin();
+            loop {
+                let mut line = String::new();
+                let event = match stdin.read_line(&mut line) {
+                    Ok(0) | Err(_) => InputEvent::Eof,
+                    Ok(_) => InputEvent::Line(line),
+                };
+                let eof = matches!(event, InputEvent::Eof);
+                if lines.send(event).is_err() || eof {
+                    return;
+                }
+            }
+        });
+        (input, sender)
+    }
+
+    // counter a signal handler can bump when it sends `InputEvent::Interrupt`.
+    // it is reset every time a line of input arrives, so it says how many
+    // interrupts have happened in a row
+    pub fn interrupts(&self) -> Arc<AtomicUsize> {
+        self.interrupts.clone()
+    }
+
+    // true from an `InputEvent::Interrupt` until the next line, so whoever
+    // reads can tell an interrupted read from the input closing
+    pub fn interrupted(&self) -> Arc<AtomicBool> {
+        self.interrupted.clone()
+    }
+}
+
+impl ChannelInput {
+    // the buffer must have been read to the end
+    fn take(&mut self, event: InputEvent) {
+        self.buffer.clear();
+        self.pos = 0;
+        match event {
+            InputEvent::Line(line) => {
+                self.interrupts.store(0, Ordering::SeqCst);
+                self.interrupted.store(false, Ordering::SeqCst);
+                self.buffer = line.into_bytes();
+            }
+            // leave the buffer empty, which reads as end of input
+            InputEvent::Interrupt => self.interrupted.store(true, Ordering::SeqCst),
+            InputEvent::Eof => self.closed = true,
+        }
+    }
+}
+
+impl TimedInput for ChannelInput {
+    fn wait(&mut self, timeout: Duration) -> io::Result<bool> {
+        if self.pos < self.buffer.len() || self.closed {
+            return Ok(true);
+        }
+        match self.events.recv_timeout(timeout) {
+            // nothing to read, but the next read ends there
+            Ok(InputEvent::Interrupt) => {
+                self.take(InputEvent::Interrupt);
+                self.cut_short = true;
+            }
+            Ok(event) => self.take(event),
+            Err(RecvTimeoutError::Timeout) => return Ok(false),
+            Err(RecvTimeoutError::Disconnected) => self.take(InputEvent::Eof),
+        }
+        Ok(true)
+    }
+}
+
+impl Read for ChannelInput {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = {
+            let available = self.fill_buf()?;
+            let n = available.len().min(buf.len());
+            buf[..n].copy_from_slice(&available[..n]);
+            n
+        };
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl BufRead for ChannelInput {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.cut_short {
+            self.cut_short = false;
+        } else if self.pos >= self.buffer.len() && !self.closed {
+            self.buffer.clear();
+            self.pos = 0;
+            let event = self.events.recv().unwrap_or(InputEvent::Eof);
+            self.take(event);
+        }
+        Ok(&self.buffer[self.pos..])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.pos += amt;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lines_come_through() {
+        let (mut input, sender) = ChannelInput::new();
+        sender.send(InputEvent::Line(String::from("cat\n"))).unwrap();
+        sender.send(InputEvent::Line(String::from("dog\n"))).unwrap();
+        let mut line = String::new();
+        input.read_line(&mut line).unwrap();
+        input.read_line(&mut line).unwrap();
+        assert_eq!(line, "cat\ndog\n");
+    }
+
+    #[test]
+    fn interrupt_ends_one_read() {
+        let (mut input, sender) = ChannelInput::new();
+        let interrupted = input.interrupted();
+        sender.send(InputEvent::Interrupt).unwrap();
+        sender.send(InputEvent::Line(String::from("cat\n"))).unwrap();
+        let mut line = String::new();
+        assert_eq!(input.read_line(&mut line).unwrap(), 0);
+        assert!(interrupted.load(Ordering::SeqCst));
+        assert_eq!(input.read_line(&mut line).unwrap(), 4);
+        assert!(!interrupted.load(Ordering::SeqCst));
+        assert_eq!(line, "cat\n");
+    }
+
+    #[test]
+    fn waiting_gives_up_in_time() {
+        let (mut input, sender) = ChannelInput::new();
+        let wait = Duration::from_millis(10);
+        assert!(!input.wait(wait).unwrap());
+        sender.send(InputEvent::Line(String::from("cat\n"))).unwrap();
+        sender.send(InputEvent::Interrupt).unwrap();
+        assert!(input.wait(wait).unwrap());
+        let mut line = String::new();
+        assert_eq!(input.read_line(&mut line).unwrap(), 4);
+        // the interrupt still ends a read
+        assert!(input.wait(wait).unwrap());
+        assert_eq!(input.read_line(&mut line).unwrap(), 0);
+        assert!(input.interrupted().load(Ordering::SeqCst));
+        drop(sender);
+        assert!(input.wait(wait).unwrap());
+        assert_eq!(input.read_line(&mut line).unwrap(), 0);
+    }
+
+    #[test]
+    fn eof_is_final() {
+        let (mut input, sender) = ChannelInput::new();
+        sender.send(InputEvent::Eof).unwrap();
+        sender.send(InputEvent::Line(String::from("cat\n"))).unwrap();
+        let mut line = String::new();
+        assert_eq!(input.read_line(&mut line).unwrap(), 0);
+        assert_eq!(input.read_line(&mut line).unwrap(), 0);
+    }
+
+    #[test]
+    fn closed_channel_is_eof() {
+        let (mut input, sender) = ChannelInput::new();
+        drop(sender);
+        let mut line = String::new();
+        assert_eq!(input.read_line(&mut line).unwrap(), 0);
+    }
+
+    #[test]
+    fn lines_reset_the_interrupt_count() {
+        let (mut input, sender) = ChannelInput::new();
+        let interrupts = input.interrupts();
+        interrupts.store(1, Ordering::SeqCst);
+        sender.send(InputEvent::Line(String::from("cat\n"))).unwrap();
+        let mut line = String::new();
+        input.read_line(&mut line).unwrap();
+        assert_eq!(interrupts.load(Ordering::SeqCst), 0);
+    }
+}
@@ -0,0 +1,255 @@
+//! A round written down as it's played, one JSON line per guess, so a crash
+//! or a kill doesn't lose it. Every line is written and synced before the
+//! game carries on. The journal is removed when the round ends normally,
+//! so one that's still there is a round that never finished, and
+//! `boggle recover` can score it
+
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use serde_json;
+
+use board::Board;
+use dictionary::Dictionary;
+use game::{Game, Player};
+use history::{self, GameRecord};
+use rules::Rules;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Entry {
+    // always the first line
+    Start {
+        // see `Board::code`
+        board: String,
+        seed: Option<u64>,
+        player: String,
+        rules: Rules,
+        minutes: f64,
+        // unix time, in seconds
+        started_at: u64,
+    },
+    // a word typed in, whether it counted or not
+    Guess { word: String, at_secs: u64 },
+    // `:undo`, or taking a suggestion instead of the last guess
+    Undo,
+    // points lost to a hint
+    Penalty { points: usize },
+}
+
+// journal.jsonl in the data directory, or in the current one without a home
+pub fn default_path() -> PathBuf {
+    match history::data_dir() {
+        Some(dir) => dir.join("journal.jsonl"),
+        None => PathBuf::from("boggle-journal.jsonl"),
+    }
+}
+
+pub struct Journal {
+    path: PathBuf,
+    file: File,
+}
+
+impl Journal {
+    // a new journal for `game`, replacing any at `path`. a round that's been
+    // resumed starts with the guesses it already had
+    pub fn start<P: Into<PathBuf>>(path: P, game: &Game, minutes: f64) -> io::Result<Journal> {
+        let path = path.into();
+        if let Some(dir) = path.parent() {
+            if !dir.as_os_str().is_empty() {
+                fs::create_dir_all(dir)?;
+            }
+        }
+        let file = File::create(&path)?;
+        let mut journal = Journal { path, file };
+
+        let record = GameRecord::new(game, minutes, 0, history::now());
+        journal.write(&Entry::Start {
+            board: record.board,
+            seed: record.seed,
+            player: record.player,
+            rules: record.rules,
+            minutes,
+            started_at: record.played_at,
+        })?;
+        for guess in record.guesses {
+            journal.write(&Entry::Guess {
+                word: guess.word,
+                at_secs: guess.at_secs.unwrap_or(0),
+            })?;
+        }
+        if record.penalty > 0 {
+            journal.write(&Entry::Penalty {
+                points: record.penalty,
+            })?;
+        }
+        Ok(journal)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    // one line, on disk before this returns
+    pub fn write(&mut self, entry: &Entry) -> io::Result<()> {
+        let mut line = serde_json::to_string(entry).map_err(io::Error::other)?;
+        line.push('\n');
+        self.file.write_all(line.as_bytes())?;
+        self.file.sync_data()
+    }
+
+    // the round is over and kept elsewhere, so the journal can go
+    pub fn finish(self) -> io::Result<()> {
+        drop(self.file);
+        fs::remove_file(&self.path)
+    }
+}
+
+// a round put back together from its journal
+pub struct Recovered {
+    pub game: Game,
+    pub minutes: f64,
+    // when the last guess was made, the journal doesn't know when the round stopped
+    pub seconds_played: u64,
+    pub started_at: u64,
+}
+
+impl Recovered {
+    // the round as it would have gone in the history
+    pub fn record(&self) -> GameRecord {
+        let played_at = self.started_at + self.seconds_played;
+        GameRecord::new(&self.game, self.minutes, self.seconds_played, played_at)
+    }
+}
+
+// replay the journal at `path`. a line that can't be read, like the half
+// a crash left behind, is skipped
+pub fn recover<P: AsRef<Path>>(
+    path: P,
+    dictionary: Option<Arc<Dictionary>>,
+) -> io::Result<Recovered> {
+    let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidData, message);
+    let mut entries = vec![];
+    for line in BufReader::new(File::open(path)?).lines() {
+        if let Ok(entry) = serde_json::from_str::<Entry>(&line?) {
+            entries.push(entry);
+        }
+    }
+
+    let mut entries = entries.into_iter();
+    let mut recovered = match entries.next() {
+        Some(Entry::Start {
+            board,
+            seed,
+            player,
+            rules,
+            minutes,
+            started_at,
+        }) => {
+            let board = Board::from_code_and_seed(&board, seed)
+                .map_err(|e| invalid(e.to_string()))?;
+            let mut game = Game::new(board, Player::new(player), dictionary);
+            game.rules = rules;
+            Recovered {
+                game,
+                minutes,
+                seconds_played: 0,
+                started_at,
+            }
+        }
+        _ => return Err(invalid(String::from("the journal doesn't start with a round"))),
+    };
+
+    for entry in entries {
+        let game = &mut recovered.game;
+        match entry {
+            Entry::Guess { word, at_secs } => {
+                let _ = game.add_guess_at(word, at_secs);
+                recovered.seconds_played = recovered.seconds_played.max(at_secs);
+            }
+            Entry::Undo => {
+                game.guesses.undo();
+            }
+            Entry::Penalty { points } => game.guesses.add_penalty(points),
+            // only one round to a journal
+            Entry::Start { .. } => break,
+        }
+    }
+    Ok(recovered)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::fs::OpenOptions;
+    use std::process;
+
+    fn game() -> Game {
+        let board = Board::cats_and_dog();
+        let dictionary = Arc::new(Dictionary::from_words(vec!["cat", "cats", "dog"]));
+        Game::new(board, Player::new(String::from("eric")), Some(dictionary))
+    }
+
+    fn path(name: &str) -> PathBuf {
+        env::temp_dir()
+            .join(format!("boggle-journal-{}", process::id()))
+            .join(name)
+    }
+
+    #[test]
+    fn a_journal_replays_the_round() {
+        let path = path("replay.jsonl");
+        let mut game = game();
+        let mut journal = Journal::start(&path, &game, 3.0).unwrap();
+        for &(word, at_secs) in &[("cats", 4), ("zebra", 9), ("dog", 12), ("tac", 20)] {
+            let _ = game.add_guess_at(word.to_string(), at_secs);
+            let entry = Entry::Guess {
+                word: word.to_string(),
+                at_secs,
+            };
+            journal.write(&entry).unwrap();
+        }
+        game.guesses.undo();
+        journal.write(&Entry::Undo).unwrap();
+        journal.write(&Entry::Penalty { points: 1 }).unwrap();
+        // killed halfway through a line
+        drop(journal);
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(b"{\"type\":\"guess\",\"wo").unwrap();
+
+        let recovered = recover(&path, game.dictionary.clone()).unwrap();
+        let found: Vec<&str> = recovered.game.guesses.valid().iter().map(|g| g.word()).collect();
+        assert_eq!(found, vec!["cats", "dog"]);
+        assert!(recovered.game.guesses.not_in_dict().is_empty());
+        assert_eq!(recovered.game.guesses.invalid().len(), 1);
+        assert_eq!(recovered.game.guesses.score(), 1);
+        assert_eq!(recovered.game.player.name, "eric");
+        assert_eq!(recovered.seconds_played, 20);
+        assert_eq!(recovered.minutes, 3.0);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn resumed_rounds_start_with_their_guesses() {
+        let path = path("resumed.jsonl");
+        let mut game = game();
+        let _ = game.add_guess_at(String::from("cat"), 3);
+        let _ = game.add_guess_at(String::from("cat"), 5);
+        let journal = Journal::start(&path, &game, 1.0).unwrap();
+
+        let recovered = recover(&path, game.dictionary.clone()).unwrap();
+        assert_eq!(recovered.game.guesses.valid().len(), 1);
+        assert_eq!(recovered.game.guesses.duplicates().len(), 1);
+        assert_eq!(recovered.record().guesses.len(), 2);
+
+        journal.finish().unwrap();
+        assert!(!path.exists());
+        fs::write(&path, "not a journal\n").unwrap();
+        let error = recover(&path, None).err().unwrap();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        fs::remove_file(&path).unwrap();
+    }
+}
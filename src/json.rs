@@ -0,0 +1,265 @@
+//! `boggle --json`: a round for programs instead of people. There are no
+//! prompts and no terminal tricks, just the messages from `protocol`, one
+//! per line: the board, then a verdict for every guess, then the results.
+//! Guesses are read one per line, like in a normal round. The round ends
+//! on time even if the other program has gone quiet
+
+use std::io::{self, Write};
+use std::slice;
+use std::sync::Arc;
+use std::time::Duration;
+
+use board::Board;
+use clock::Clock;
+use command::Command;
+use dictionary::Dictionary;
+use export::{Export, Report};
+use game::{Game, Player};
+use history;
+use input::TimedInput;
+use multiplayer::Cancellation;
+use protocol::{self, Message};
+use rules::Rules;
+use timer::Timer;
+
+// how often the clock is checked while waiting for a line
+const POLL: Duration = Duration::from_millis(100);
+
+pub struct JsonSession<R, W, C> {
+    input: R,
+    output: W,
+    clock: C,
+    dictionary: Option<Arc<Dictionary>>,
+    // a random 4x4 board unless given one
+    board: Option<Board>,
+    player: Player,
+    minutes: f64,
+    rules: Rules,
+    // the file given with --output, if any
+    export: Option<Export>,
+}
+
+impl<R: TimedInput, W: Write, C: Clock + Clone> JsonSession<R, W, C> {
+    pub fn new(
+        input: R,
+        output: W,
+        clock: C,
+        dictionary: Option<Dictionary>,
+    ) -> JsonSession<R, W, C> {
+        JsonSession {
+            input,
+            output,
+            clock,
+            dictionary: dictionary.map(Arc::new),
+            board: None,
+            player: Player::new(String::from("player")),
+            minutes: 3.0,
+            rules: Rules::default(),
+            export: None,
+        }
+    }
+
+    pub fn use_board(&mut self, board: Board) {
+        self.board = Some(board);
+    }
+
+    pub fn set_player(&mut self, name: &str) {
+        self.player = Player::new(name.to_string());
+    }
+
+    pub fn set_minutes(&mut self, minutes: f64) {
+        self.minutes = minutes;
+    }
+
+    pub fn set_rules(&mut self, rules: Rules) {
+        self.rules = rules;
+    }
+
+    // also write the finished round to `export`
+    pub fn set_export(&mut self, export: Export) {
+        self.export = Some(export);
+    }
+
+    pub fn output(&self) -> &W {
+        &self.output
+    }
+
+    // play one round: it ends when time is up, on `:quit`,
+    // or when the input closes. returns the finished game
+    pub fn run(&mut self) -> io::Result<Game> {
+        let board = self.board.take().unwrap_or_default();
+        let mut timer = Timer::with_clock(self.clock.clone());
+        timer.set_play_time(self.minutes);
+        let mut game = Game::new(board, self.player.clone(), self.dictionary.clone());
+        game.rules = self.rules.clone();
+
+        self.send(&Message::Board {
+            board: game.board.code(),
+            seed: game.board.seed(),
+            seconds: timer.get_remaining_time().max(0) as u64,
+        })?;
+        self.send(&Message::Start)?;
+        timer.start();
+
+        loop {
+            if timer.is_time_up() {
+                break;
+            }
+            if !self.input.wait(POLL)? {
+                continue;
+            }
+            let mut line = String::new();
+            if self.input.read_line(&mut line)? == 0 {
+                break;
+            }
+            let line = line.trim();
+            if timer.is_time_up() {
+                if !line.is_empty() {
+                    self.send(&Message::Error {
+                        message: format!("{} came in after time was up", line),
+                    })?;
+                }
+                break;
+            }
+            match Command::parse(line) {
+                None if line.is_empty() => (),
+                None => {
+                    let result = game.add_guess_at(line.to_string(), timer.elapsed_secs());
+                    let verdict = Message::verdict(line.to_string(), result, game.guesses.score());
+                    self.send(&verdict)?;
+                }
+                Some(Command::Time) => self.send(&Message::Tick {
+                    seconds_left: timer.get_remaining_time().max(0) as u64,
+                })?,
+                Some(Command::Quit) => break,
+                // the rest are for people, but they still get an answer
+                Some(_) => self.send(&Message::Error {
+                    message: format!("{} doesn't work here, only :time and :quit do", line),
+                })?,
+            }
+        }
+
+        self.send(&Message::End)?;
+        let results = Cancellation::new(slice::from_ref(&game));
+        self.send(&Message::results(&results))?;
+        if let Some(ref mut export) = self.export {
+            export.add(Report::new(&mut game, self.minutes, timer.elapsed_secs(), history::now()))?;
+        }
+        Ok(game)
+    }
+
+    fn send(&mut self, message: &Message) -> io::Result<()> {
+        protocol::write_message(&mut self.output, message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clock::{ManualClock, SystemClock};
+    use input::{ChannelInput, InputEvent};
+    use std::io::BufRead;
+
+    // lines typed ten seconds apart
+    struct Slow {
+        clock: ManualClock,
+        lines: Vec<&'static str>,
+    }
+
+    impl io::Read for Slow {
+        fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+            unreachable!()
+        }
+    }
+
+    impl TimedInput for Slow {
+        fn wait(&mut self, _: Duration) -> io::Result<bool> {
+            Ok(true)
+        }
+    }
+
+    impl BufRead for Slow {
+        fn fill_buf(&mut self) -> io::Result<&[u8]> {
+            unreachable!()
+        }
+
+        fn consume(&mut self, _: usize) {}
+
+        fn read_line(&mut self, buf: &mut String) -> io::Result<usize> {
+            if self.lines.is_empty() {
+                return Ok(0);
+            }
+            self.clock.advance(Duration::from_secs(10));
+            let line = self.lines.remove(0);
+            buf.push_str(line);
+            buf.push('\n');
+            Ok(line.len() + 1)
+        }
+    }
+
+    fn play(lines: Vec<&'static str>) -> (Game, Vec<String>) {
+        let clock = ManualClock::new();
+        let input = Slow {
+            clock: clock.clone(),
+            lines,
+        };
+        let dictionary = Dictionary::from_words(vec!["cat", "cats", "dog"]);
+        let mut session = JsonSession::new(input, vec![], clock, Some(dictionary));
+        session.use_board(Board::cats_and_dog());
+        session.set_player("bot");
+        session.set_minutes(1.0);
+        let game = session.run().unwrap();
+        let output = String::from_utf8(session.output().clone()).unwrap();
+        (game, output.lines().map(String::from).collect())
+    }
+
+    #[test]
+    fn every_line_but_a_blank_one_gets_an_answer() {
+        // the last one comes after the minute is up
+        let (game, lines) = play(vec!["cats", "", ":time", "dog", ":undo", "cat"]);
+        assert_eq!(
+            lines,
+            vec![
+                r#"{"type":"board","board":"4x4:CATSXXXXDOGXXXXX","seed":null,"seconds":60}"#,
+                r#"{"type":"start"}"#,
+                r#"{"type":"verdict","word":"cats","points":1,"rejection":null,"reason":null,"score":1}"#,
+                r#"{"type":"tick","seconds_left":30}"#,
+                r#"{"type":"verdict","word":"dog","points":1,"rejection":null,"reason":null,"score":2}"#,
+                r#"{"type":"error","message":":undo doesn't work here, only :time and :quit do"}"#,
+                r#"{"type":"error","message":"cat came in after time was up"}"#,
+                r#"{"type":"end"}"#,
+                r#"{"type":"results","players":["bot"],"words":[["cats",[0]],["dog",[0]]],"scores":[2]}"#,
+            ]
+        );
+        assert_eq!(game.guesses.score(), 2);
+    }
+
+    #[test]
+    fn rejections_come_with_reasons_and_quit_ends_early() {
+        let (_, lines) = play(vec!["zebra", ":quit", "dog"]);
+        assert_eq!(
+            lines[2],
+            r#"{"type":"verdict","word":"zebra","points":0,"rejection":{"kind":"letter_not_on_board","letter":"Z"},"reason":"the letter Z is not on the board","score":0}"#
+        );
+        assert_eq!(lines[3], r#"{"type":"end"}"#);
+        assert_eq!(lines.len(), 5);
+    }
+
+    #[test]
+    fn the_round_ends_on_time_when_input_stops() {
+        let (input, sender) = ChannelInput::new();
+        sender.send(InputEvent::Line(String::from("cats\n"))).unwrap();
+        let dictionary = Dictionary::from_words(vec!["cat", "cats", "dog"]);
+        let mut session = JsonSession::new(input, vec![], SystemClock::new(), Some(dictionary));
+        session.use_board(Board::cats_and_dog());
+        // a second, and nothing more is typed but the input stays open
+        session.set_minutes(1.0 / 60.0);
+        let game = session.run().unwrap();
+        drop(sender);
+        assert_eq!(game.guesses.score(), 1);
+        let output = String::from_utf8(session.output().clone()).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[3], r#"{"type":"end"}"#);
+    }
+}
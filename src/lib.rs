@@ -0,0 +1,60 @@
+//! Boggle® board generation, word validation and scoring.
+//! The `boggle` binary is a thin command-line front-end over this crate
+
+extern crate rand;
+extern crate serde;
+#[macro_use]
+extern crate serde_derive;
+extern crate serde_json;
+extern crate term;
+
+pub mod arena;
+pub mod board;
+pub mod clock;
+pub mod command;
+pub mod console;
+pub mod dice;
+pub mod dictionary;
+pub mod error;
+pub mod export;
+pub mod frequency;
+pub mod game;
+pub mod hint;
+pub mod history;
+pub mod input;
+pub mod journal;
+pub mod json;
+pub mod multiplayer;
+pub mod net;
+pub mod opponent;
+pub mod profile;
+pub mod protocol;
+pub mod rejection;
+pub mod save;
+pub mod session;
+pub mod rules;
+pub mod solver;
+pub mod strategy;
+pub mod suggest;
+pub mod timer;
+
+pub use board::{Board, BoardError, Piece};
+pub use clock::{Clock, ManualClock, SystemClock};
+pub use console::Console;
+pub use dice::Dice;
+pub use dictionary::Dictionary;
+pub use error::Error;
+pub use frequency::Frequencies;
+pub use command::Command;
+pub use game::{Bucket, Game, Guess, Guesses, Player};
+pub use hint::{Hint, HintKind};
+pub use history::{GameRecord, History, Stats};
+pub use input::{ChannelInput, InputEvent, TimedInput};
+pub use multiplayer::Cancellation;
+pub use opponent::{Computer, Difficulty, Opponent};
+pub use profile::{Profile, ProfileStore, Theme};
+pub use rejection::Rejection;
+pub use rules::Rules;
+pub use session::GameSession;
+pub use strategy::Strategy;
+pub use timer::Timer;
@@ -1,131 +1,420 @@
-/// Simple command-line version of Boggle®
+//! Simple command-line version of Boggle®
 
-extern crate term;
-extern crate rand;
+extern crate boggle;
+extern crate ctrlc;
 
-use std::io::{self, Write, BufReader, BufRead};
-use std::fs::File;
+use std::env;
+use std::fs;
+use std::io::{self, BufRead, BufReader, Write};
+use std::process;
+use std::sync::atomic::Ordering;
+use std::time::{SystemTime, UNIX_EPOCH};
 
-mod board;
-mod game;
-mod timer;
+use std::sync::Arc;
 
-use board::Board;
-use game::{Game, Player, Guesses};
-use timer::Timer;
+use boggle::arena::{self, ArenaSettings};
+use boggle::export::Export;
+use boggle::journal;
+use boggle::json::JsonSession;
+use boggle::net::{Client, Server};
+use boggle::save::{self, SavedGame};
+use boggle::session::parse_board_size;
+use boggle::{Board, ChannelInput, Computer, Console, Dice, Dictionary, Difficulty, Error,
+             Frequencies, GameSession, History, InputEvent, Profile, ProfileStore, Rules, Stats,
+             Strategy, SystemClock};
+
+// where `boggle host` listens unless told otherwise. only this machine,
+// since anyone who can connect can play: `--addr 0.0.0.0:7878` opens it up
+const DEFAULT_HOST_ADDR: &str = "127.0.0.1:7878";
 
 fn main() {
-    println!("Welcome to Boggle®");
-    println!("==================\n");
-
-    // a dictionary is not required to play the game,
-    // but user should understand the consequences
-    let dictionary: Option<Vec<String>> = match File::open("dictionary.txt") {
-        Ok(file) => {
-            // TODO?: process this in a thread?
-            let lines = BufReader::new(file)
-                .lines()
-                .filter(|line| match line {
-                    &Ok(ref _l) => true,
-                    &Err(_) => false,
-                })
-                .map(|line| line.unwrap().to_uppercase())
-                .collect();
-            Some(lines)
-        }
-        Err(_) => {
-            let mut t = term::stdout().unwrap();
-
-            t.fg(term::color::YELLOW).unwrap();
-            (write!(
-                t,
-                "\nWARNING: There was no dictionary file available! \
-                \nThe game will not check that your words exist in the dictionary.\n \
-                \nFor future games, please put a text file named \"dictionary.txt\" \
-                in the same directory as this program. \
-                \nExample dictionary: \
-                \nhttps://www.wordgamedictionary.com/twl06/download/twl06.txt\n\n\n"
-            )).unwrap();
-            t.reset().unwrap();
-            None
+    let args: Vec<String> = env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        Some("stats") => return show_stats(),
+        Some("host") => return host(&args[1..]),
+        Some("join") => return join(&args[1..]),
+        Some("arena") => return run_arena(&args[1..]),
+        Some("recover") => return recover(&args[1..]),
+        _ => (),
+    }
+
+    // `boggle resume [file]`: carry on with a saved round
+    let resume = match args.first().map(String::as_str) {
+        Some("resume") => {
+            // --json only deals new boards
+            if args.iter().any(|arg| arg == "--json") {
+                eprintln!("A saved round can't be resumed with --json, leave it out to carry on");
+                process::exit(2);
+            }
+            let path = match args.get(1) {
+                Some(path) if !path.starts_with("--") => path.into(),
+                _ => save::default_path(),
+            };
+            match SavedGame::load(&path) {
+                Ok(saved) => Some((path, saved)),
+                Err(e) => {
+                    eprintln!("Couldn't resume from {}: {}", path.display(), e);
+                    process::exit(1);
+                }
+            }
+        }
+        _ => None,
+    };
+
+    // every round is also written here, as JSON or CSV
+    let export = flag_value(&args, "--output").map(|path| match Export::new(path) {
+        Some(export) => export,
+        None => {
+            eprintln!("--output needs a file name ending in .json or .csv");
+            process::exit(2);
+        }
+    });
+
+    // TODO?: process this in a thread?
+    let dictionary = Dictionary::open("dictionary.txt").ok();
+
+    // Ctrl-C (or SIGTERM) ends the round early, saves it for `boggle resume`
+    // and shows the results, a second one in a row exits right away
+    let (input, sender) = ChannelInput::stdin();
+    let interrupts = input.interrupts();
+    let handler = ctrlc::set_handler(move || {
+        if interrupts.fetch_add(1, Ordering::SeqCst) > 0 {
+            process::exit(130);
+        }
+        let _ = sender.send(InputEvent::Interrupt);
+    });
+    if let Err(e) = handler {
+        eprintln!("Warning: Ctrl-C will exit without showing results ({})", e);
+    }
+
+    if args.iter().any(|arg| arg == "--json") {
+        return play_json(&args, input, dictionary, export);
+    }
+
+    // plain output when stdout is piped or not a capable terminal
+    let console = Console::stdout();
+    let interrupted = input.interrupted();
+    let mut session = GameSession::new(input, console, SystemClock::new(), dictionary);
+    session.set_interrupted(interrupted);
+    if let Ok(frequencies) = Frequencies::open("frequency.txt") {
+        session.set_frequencies(frequencies);
+    }
+    // practice rounds let you take a "did you mean" by pressing enter,
+    // hints are free unless they're given a price
+    session.set_rules(Rules {
+        practice: args.iter().any(|arg| arg == "--practice"),
+        hint_points: number_flag(&args, "--hint-points", 0),
+        hint_seconds: number_flag(&args, "--hint-seconds", 0),
+        ..Rules::default()
+    });
+    if let Some(export) = export {
+        session.set_export(export);
+    }
+    // every finished game is saved for `boggle stats`
+    if let Some(history) = History::open_default() {
+        session.set_history(history);
+    }
+    // rounds are journaled as they're played, but not over a round that
+    // never finished, that one waits for `boggle recover`
+    let journal = journal::default_path();
+    if journal.exists() {
+        eprintln!("Your last round didn't finish, `boggle recover` can still score it.");
+        eprintln!("Until then, a crash during this sitting would lose the round.\n");
+    } else {
+        session.set_journal(journal);
+    }
+    // pick who's playing at the start, or name them with --profile
+    if let Some(profiles) = ProfileStore::open_default() {
+        if let Some(name) = flag_value(&args, "--profile") {
+            match profiles.find(name) {
+                Ok(found) => session.use_profile(found.unwrap_or_else(|| Profile::new(name))),
+                Err(e) => {
+                    eprintln!("Couldn't read {}: {}", profiles.path().display(), e);
+                    process::exit(1);
+                }
+            }
+        }
+        session.set_profiles(profiles);
+    }
+    let result = match resume {
+        Some((path, saved)) => {
+            session.set_save_path(path);
+            session.resume(saved)
         }
+        None => session.run(),
     };
+    match result {
+        Ok(()) => (),
+        Err(Error::Eof) => println!("\nNo more input, goodbye!"),
+        Err(e) => {
+            eprintln!("\nError: {}", e);
+            process::exit(1);
+        }
+    }
+}
 
-    // to "prompt" on the same line, call print! macro followed by a flush
-    print!("For how many minutes would you like to play? (decimals OK) ");
-    io::stdout().flush().unwrap();
-
-    let mut timer = Timer::new();
-    timer.get_user_play_time();
-    clean_prev_line();
-
-    print!("Please enter your name: ");
-    io::stdout().flush().unwrap();
-
-    // read name
-    let mut name = String::new();
-    io::stdin().read_line(&mut name).expect(
-        "Failed to read line",
-    );
-    clean_prev_line();
-
-    // create game with a new board
-    let mut game = Game {
-        board: Board::new(dictionary),
-        player: Player::new(String::from(name.trim())),
-        guesses: Guesses::new(),
+// `boggle stats`: lifetime numbers from the saved history
+fn show_stats() {
+    let history = match History::open_default() {
+        Some(history) => history,
+        None => {
+            eprintln!("Can't find your history, set HOME or XDG_DATA_HOME");
+            process::exit(1);
+        }
     };
+    match history.load() {
+        Ok(ref records) if records.is_empty() => println!("No games played yet"),
+        Ok(records) => print!("{}", Stats::from_records(&records)),
+        Err(e) => {
+            eprintln!("Couldn't read {}: {}", history.path().display(), e);
+            process::exit(1);
+        }
+    }
+}
 
-    println!("Hello {}, here is your game:", game.player.name);
-    println!(
-        "Enter as many words as possible in {} mins!",
-        timer.max_time_minutes
-    );
+// `boggle recover [file]`: score a round that crashed or was killed,
+// from its journal, and put it in the history
+fn recover(args: &[String]) {
+    let path = match args.first() {
+        Some(path) if !path.starts_with("--") => path.into(),
+        _ => journal::default_path(),
+    };
+    if !path.exists() {
+        println!("No round to recover, every round finished");
+        return;
+    }
+    let dictionary = Dictionary::open("dictionary.txt").ok().map(Arc::new);
+    let recovered = match journal::recover(&path, dictionary) {
+        Ok(recovered) => recovered,
+        Err(e) => {
+            eprintln!("Couldn't recover the round in {}: {}", path.display(), e);
+            process::exit(1);
+        }
+    };
+    let game = &recovered.game;
+    println!("{}'s round, {} seconds in:", game.player.name, recovered.seconds_played);
     println!("{}", game.board);
+    println!("{}", game.guesses);
 
-    timer.start();
-    loop {
-        if timer.is_time_up() {
-            break;
+    if let Some(history) = History::open_default() {
+        if let Err(e) = history.append(&recovered.record()) {
+            eprintln!("Couldn't save this game to {}: {}", history.path().display(), e);
+            process::exit(1);
         }
-        println!(
-            "Now start typing words! ({} seconds left)",
-            timer.get_remaining_time()
-        );
+    }
+    if let Some(profiles) = ProfileStore::open_default() {
+        if let Ok(Some(mut profile)) = profiles.find(&game.player.name) {
+            profile.stats.record(game);
+            if let Err(e) = profiles.save(&profile) {
+                eprintln!("Couldn't update {}: {}", profiles.path().display(), e);
+            }
+        }
+    }
+    // only once it's safely in the history
+    if let Err(e) = fs::remove_file(&path) {
+        eprintln!("Couldn't remove {}: {}", path.display(), e);
+    }
+}
 
-        // get guess
-        let mut line = String::new();
-        io::stdin().read_line(&mut line).expect(
-            "Failed to read line",
-        );
+// the argument after `flag`, e.g. "eric" in `--profile eric`
+fn flag_value<'a>(args: &'a [String], flag: &str) -> Option<&'a str> {
+    let at = args.iter().position(|arg| arg == flag)?;
+    args.get(at + 1).map(String::as_str)
+}
 
-        // add to guesses if time is remaining
-        // (i.e. don't allow guesses after time has run out)
-        clean_prev_line();
-        clean_prev_line();
+// `boggle host`: deal a board to everyone who joins and referee the round
+fn host(args: &[String]) {
+    let addr = flag_value(args, "--addr").unwrap_or(DEFAULT_HOST_ADDR);
+    let players = number_flag(args, "--players", 2);
+    let minutes = number_flag(args, "--minutes", 3.0);
+    let dictionary = Dictionary::open("dictionary.txt").ok();
+    if dictionary.is_none() {
+        println!("No dictionary.txt, so any word on the board counts");
+    }
 
-        if timer.is_time_up() {
-            println!(
-                "Oooh, so close! But \"{}\" was entered after time ran out",
-                line.trim()
-            );
-            break;
-        } else {
-            game.add_guess(String::from(line.trim()));
+    let mut server = match Server::bind(addr, dictionary) {
+        Ok(server) => server,
+        Err(e) => {
+            eprintln!("Couldn't listen on {}: {}", addr, e);
+            process::exit(1);
         }
+    };
+    if let Ok(addr) = server.local_addr() {
+        println!("Waiting for {} players on {}", players, addr);
     }
+    let result = server.wait_for_players(players).and_then(|()| {
+        println!("Playing with {}", server.players().join(", "));
+        server.play_round(Board::new(), minutes)
+    });
+    match result {
+        Ok(cancellation) => println!("\n{}", cancellation),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+    }
+}
 
-    println!("Nice job! Here are your results:");
-    println!("{}", game.guesses);
-    println!("\nPress enter to exit the program");
-    let mut end = String::new();
-    io::stdin().read_line(&mut end).expect(
-        "Failed to read line",
-    );
+// `boggle join <addr>`: play on someone else's board
+fn join(args: &[String]) {
+    let addr = match args.first() {
+        Some(addr) if !addr.starts_with("--") => addr,
+        _ => {
+            eprintln!("Usage: boggle join <host:port> [--name <name>]");
+            process::exit(2);
+        }
+    };
+    let mut input = BufReader::new(io::stdin());
+    let name = match flag_value(args, "--name").or_else(|| flag_value(args, "--profile")) {
+        Some(name) => name.to_string(),
+        None => {
+            print!("Please enter your name: ");
+            let _ = io::stdout().flush();
+            let mut name = String::new();
+            if input.read_line(&mut name).unwrap_or(0) == 0 {
+                return;
+            }
+            name.trim().to_string()
+        }
+    };
+
+    let result = Client::connect(addr.as_str(), &name)
+        .and_then(|client| client.play(input, &mut Console::stdout()));
+    if let Err(e) = result {
+        eprintln!("Error: {}", e);
+        process::exit(1);
+    }
 }
 
-fn clean_prev_line() {
-    let mut term = term::stdout().unwrap();
-    term.cursor_up().unwrap();
-    term.delete_line().unwrap();
+// `boggle --json`: one round for another program to play,
+// in JSON lines on stdin and stdout
+fn play_json(
+    args: &[String],
+    input: ChannelInput,
+    dictionary: Option<Dictionary>,
+    export: Option<Export>,
+) {
+    let (rows, cols) = size_flag(args);
+    let board = match flag_value(args, "--seed") {
+        Some(_) => Board::from_seed(rows, cols, number_flag(args, "--seed", 0)),
+        None => Board::random(rows, cols),
+    };
+    let mut session = JsonSession::new(input, io::stdout(), SystemClock::new(), dictionary);
+    session.use_board(board);
+    session.set_minutes(number_flag(args, "--minutes", 3.0));
+    if let Some(name) = flag_value(args, "--name") {
+        session.set_player(name);
+    }
+    if let Some(export) = export {
+        session.set_export(export);
+    }
+    if let Err(e) = session.run() {
+        eprintln!("Error: {}", e);
+        process::exit(1);
+    }
+}
+
+// `boggle arena`: computer players against each other, many times over.
+// the levels to play are listed by name, e.g. `boggle arena easy hard`
+fn run_arena(args: &[String]) {
+    let mut levels = vec![];
+    let mut rest = args.iter();
+    while let Some(arg) = rest.next() {
+        if arg.starts_with("--") {
+            // skip the flag's value
+            rest.next();
+            continue;
+        }
+        match Difficulty::from_name(arg) {
+            Some(level) => levels.push(level),
+            None => {
+                eprintln!("Unknown level {}, pick from easy, medium and hard", arg);
+                process::exit(2);
+            }
+        }
+    }
+    if levels.is_empty() {
+        levels = vec![Difficulty::Easy, Difficulty::Medium, Difficulty::Hard];
+    }
+    let (rows, cols) = size_flag(args);
+    let minutes: f64 = number_flag(args, "--minutes", 3.0);
+    let settings = ArenaSettings {
+        games: number_flag(args, "--games", 1000),
+        // a random first seed unless asked for one, so it's printed with the results
+        seed: number_flag(args, "--seed", rand_seed()),
+        rows,
+        cols,
+        seconds: (minutes * 60.0) as u64,
+        rules: Rules::default(),
+        dice: flag_value(args, "--dice").map(dice_flag),
+    };
+    let dictionary = match Dictionary::open("dictionary.txt") {
+        Ok(dictionary) => Arc::new(dictionary),
+        Err(e) => {
+            eprintln!("The arena needs dictionary.txt: {}", e);
+            process::exit(1);
+        }
+    };
+    let frequencies = Frequencies::open("frequency.txt").ok().map(Arc::new);
+
+    // each player gets its own seed, so two of the same level play differently
+    let mut strategies: Vec<Box<dyn Strategy>> = levels
+        .into_iter()
+        .enumerate()
+        .map(|(i, level)| {
+            let seed = settings.seed.wrapping_add(i as u64).rotate_left(32);
+            let mut computer = Computer::new(level, dictionary.clone(), seed);
+            if let Some(ref frequencies) = frequencies {
+                computer.set_frequencies(frequencies.clone());
+            }
+            Box::new(computer) as Box<dyn Strategy>
+        })
+        .collect();
+    print!("{}", arena::run(&mut strategies, dictionary, &settings));
+}
+
+// `--dice classic` for the dice in the box, anything else is a dice file
+fn dice_flag(value: &str) -> Dice {
+    if value == "classic" {
+        return Dice::classic();
+    }
+    match Dice::open(value) {
+        Ok(dice) => dice,
+        Err(e) => {
+            eprintln!("Couldn't read dice from {}: {}", value, e);
+            process::exit(2);
+        }
+    }
+}
+
+// a seed from the clock, good enough to not repeat between runs
+fn rand_seed() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() ^ u64::from(d.subsec_nanos()))
+        .unwrap_or(0)
+}
+
+// a board size given as `--size 5x4` or `--size 5`, 4x4 without one
+fn size_flag(args: &[String]) -> (usize, usize) {
+    match flag_value(args, "--size").map(parse_board_size) {
+        Some(Some(size)) => size,
+        Some(None) => {
+            eprintln!("--size needs a size like 4x4 or 5");
+            process::exit(2);
+        }
+        None => (4, 4),
+    }
+}
+
+// a number given as `--flag n`, or `default` when there isn't one
+fn number_flag<T: std::str::FromStr>(args: &[String], flag: &str, default: T) -> T {
+    match flag_value(args, flag).map(str::parse) {
+        Some(Ok(n)) => n,
+        Some(Err(_)) => {
+            eprintln!("{} needs a number", flag);
+            process::exit(2);
+        }
+        None => default,
+    }
 }
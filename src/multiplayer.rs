@@ -0,0 +1,278 @@
+//! Several players on the same board: each player's round is its own `Game`,
+//! and the results are put side by side here.
+//! As in the board game, a word found by more than one player scores nothing
+
+use std::cmp::Reverse;
+use std::fmt::{self, Display, Formatter};
+
+use game::Game;
+
+// one line of the results table
+#[derive(Debug, Clone, PartialEq)]
+pub struct Standing {
+    pub name: String,
+    pub words: usize,
+    pub score: usize,
+}
+
+pub struct ResultsTable(pub Vec<Standing>);
+
+impl Display for ResultsTable {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let standings = &self.0;
+        let width = standings
+            .iter()
+            .map(|s| s.name.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max("Player".len());
+        writeln!(f, "\nResults\n=======")?;
+        writeln!(f, "   {:<width$}  Words  Score", "Player", width = width)?;
+        let mut place = 0;
+        for (i, standing) in standings.iter().enumerate() {
+            // tied players share a place
+            if i == 0 || standings[i - 1].score != standing.score {
+                place = i + 1;
+            }
+            writeln!(
+                f,
+                "{:>2} {:<width$}  {:>5}  {:>5}",
+                place,
+                standing.name,
+                standing.words,
+                standing.score,
+                width = width
+            )?;
+        }
+        let best = standings.first().map_or(0, |s| s.score);
+        let winners: Vec<&str> = standings
+            .iter()
+            .filter(|s| s.score == best)
+            .map(|s| s.name.as_str())
+            .collect();
+        match winners.len() {
+            0 => Ok(()),
+            1 => write!(f, "\n{} wins!", winners[0]),
+            _ => write!(f, "\nIt's a tie between {}!", winners.join(" and ")),
+        }
+    }
+}
+
+// the scores once every shared word is crossed off
+pub struct Cancellation {
+    pub players: Vec<String>,
+    // every word anyone found, lowercase and sorted, with the players
+    // (indices into `players`) who found it
+    pub words: Vec<(String, Vec<usize>)>,
+    // points for words only that player found, less hint penalties
+    pub scores: Vec<usize>,
+}
+
+impl Cancellation {
+    // `games` are the players' rounds, all on the same board
+    pub fn new(games: &[Game]) -> Cancellation {
+        let mut words: Vec<(String, Vec<usize>)> = vec![];
+        for (player, game) in games.iter().enumerate() {
+            for guess in game.guesses.valid() {
+                found(&mut words, guess.word(), player);
+            }
+        }
+        words.sort();
+
+        let scores = games
+            .iter()
+            .map(|game| {
+                let points: usize = game.guesses
+                    .valid()
+                    .iter()
+                    .filter(|g| !is_shared(&words, g.word()))
+                    .map(|g| g.score())
+                    .sum();
+                points.saturating_sub(game.guesses.penalty())
+            })
+            .collect();
+
+        Cancellation {
+            players: games.iter().map(|g| g.player.name.clone()).collect(),
+            words,
+            scores,
+        }
+    }
+
+    // words only `player` found, in alphabetical order
+    pub fn unique_words(&self, player: usize) -> Vec<&str> {
+        self.words
+            .iter()
+            .filter(|&(_, found_by)| found_by.len() == 1 && found_by[0] == player)
+            .map(|(word, _)| word.as_str())
+            .collect()
+    }
+
+    // best score first, counting only the words nobody else found.
+    // a tie keeps the order they played in
+    pub fn standings(&self) -> Vec<Standing> {
+        let mut standings: Vec<Standing> = self.players
+            .iter()
+            .enumerate()
+            .map(|(i, name)| Standing {
+                name: name.clone(),
+                words: self.unique_words(i).len(),
+                score: self.scores[i],
+            })
+            .collect();
+        standings.sort_by_key(|s| Reverse(s.score));
+        standings
+    }
+}
+
+fn is_shared(words: &[(String, Vec<usize>)], word: &str) -> bool {
+    let word = word.to_lowercase();
+    words
+        .iter()
+        .any(|(w, found_by)| *w == word && found_by.len() > 1)
+}
+
+impl Display for Cancellation {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        for (i, name) in self.players.iter().enumerate() {
+            let unique = self.unique_words(i);
+            if unique.is_empty() {
+                writeln!(f, "{} found no words of their own", name)?;
+            } else {
+                writeln!(f, "Only {} found: {}", name, unique.join(", "))?;
+            }
+        }
+
+        if !self.words.is_empty() {
+            // one column per player, as wide as their name
+            let width = self.words
+                .iter()
+                .map(|(w, _)| w.chars().count())
+                .max()
+                .unwrap_or(0);
+            writeln!(f, "\nWho found what\n==============")?;
+            write!(f, "{:<width$}", "", width = width)?;
+            for name in self.players.iter() {
+                write!(f, "  {}", name)?;
+            }
+            writeln!(f)?;
+            for (word, found_by) in self.words.iter() {
+                let mut line = format!("{:<width$}", word, width = width);
+                for (i, name) in self.players.iter().enumerate() {
+                    let mark = if found_by.contains(&i) { "x" } else { "" };
+                    line.push_str(&format!("  {:^w$}", mark, w = name.chars().count()));
+                }
+                writeln!(f, "{}", line.trim_end())?;
+            }
+            writeln!(f, "(words found by more than one player score nothing)")?;
+        }
+
+        write!(f, "{}", ResultsTable(self.standings()))
+    }
+}
+
+// note that `player` found `word`. finding it again doesn't list them twice,
+// or they'd be sharing the word with themselves
+fn found(words: &mut Vec<(String, Vec<usize>)>, word: &str, player: usize) {
+    let word = word.to_lowercase();
+    match words.iter().position(|(w, _)| *w == word) {
+        Some(i) if words[i].1.contains(&player) => (),
+        Some(i) => words[i].1.push(player),
+        None => words.push((word, vec![player])),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use board::Board;
+    use game::Player;
+
+    fn game(name: &str, words: &[&str]) -> Game {
+        let board = Board::cats_and_dog();
+        let mut game = Game::new(board, Player::new(name.to_string()), None);
+        for word in words {
+            let _ = game.add_guess(word.to_string());
+        }
+        game
+    }
+
+    #[test]
+    fn best_score_first() {
+        let games = vec![
+            game("ana", &["cat"]),
+            game("eric", &["cats", "cat", "dog"]),
+            game("bo", &["dog"]),
+        ];
+        let standings = Cancellation::new(&games).standings();
+        let names: Vec<&str> = standings.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["eric", "ana", "bo"]);
+        // cat and dog are shared, only eric's cats counts
+        assert_eq!(standings[0].words, 1);
+        assert_eq!(standings[0].score, 1);
+        assert_eq!(standings[1].score, 0);
+    }
+
+    #[test]
+    fn shared_words_score_nothing() {
+        let games = vec![
+            game("ana", &["cats", "dog"]),
+            game("eric", &["DOG", "cat"]),
+            game("bo", &[]),
+        ];
+        let cancellation = Cancellation::new(&games);
+        assert_eq!(cancellation.scores, vec![1, 1, 0]);
+        assert_eq!(cancellation.unique_words(0), vec!["cats"]);
+        assert_eq!(cancellation.unique_words(1), vec!["cat"]);
+        assert!(cancellation.unique_words(2).is_empty());
+        assert_eq!(
+            cancellation.to_string(),
+            "Only ana found: cats\n\
+             Only eric found: cat\n\
+             bo found no words of their own\n\
+             \nWho found what\n==============\n\
+             \x20     ana  eric  bo\n\
+             cat         x\n\
+             cats   x\n\
+             dog    x    x\n\
+             (words found by more than one player score nothing)\n\
+             \nResults\n=======\n   Player  Words  Score\n \
+              1 ana         1      1\n \
+              1 eric        1      1\n \
+              3 bo          0      0\n\
+             \nIt's a tie between ana and eric!"
+        );
+    }
+
+    #[test]
+    fn finding_a_word_twice_is_not_sharing_it() {
+        let mut words = vec![];
+        found(&mut words, "cat", 0);
+        found(&mut words, "CAT", 0);
+        found(&mut words, "cat", 1);
+        found(&mut words, "dog", 1);
+        found(&mut words, "Dog", 1);
+        assert_eq!(
+            words,
+            vec![(String::from("cat"), vec![0, 1]), (String::from("dog"), vec![1])]
+        );
+
+        let games = vec![game("ana", &["cat", "CAT"]), game("eric", &["dog"])];
+        let cancellation = Cancellation::new(&games);
+        assert_eq!(cancellation.unique_words(0), vec!["cat"]);
+        assert_eq!(cancellation.scores, vec![1, 1]);
+    }
+
+    #[test]
+    fn table_shares_places_on_a_tie() {
+        let games = vec![game("ana", &["cat"]), game("eric", &["dog"])];
+        let table = ResultsTable(Cancellation::new(&games).standings()).to_string();
+        assert_eq!(
+            table,
+            "\nResults\n=======\n   Player  Words  Score\n \
+              1 ana         1      1\n \
+              1 eric        1      1\n\
+             \nIt's a tie between ana and eric!"
+        );
+    }
+}
@@ -0,0 +1,446 @@
+//! Several players over TCP: `boggle host` deals the board, runs the clock
+//! and checks every guess, `boggle join` is a player's terminal.
+//! What they say to each other is in `protocol`
+
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError, Sender};
+use std::sync::Arc;
+use std::str;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use board::Board;
+use console::Console;
+use dictionary::Dictionary;
+use game::{Game, Player};
+use multiplayer::Cancellation;
+use protocol::{self, read_message as receive, read_message_limited, Message, VERSION};
+use rules::Rules;
+use timer::Timer;
+
+// how often the host looks at the clock while nobody is guessing
+const POLL: Duration = Duration::from_millis(100);
+// players are told the time left whenever it's a multiple of this
+const TICK_SECS: i64 = 10;
+// how long a new connection has to say hello
+const HELLO_TIMEOUT: Duration = Duration::from_secs(5);
+// how long a line from a player can be, hello or not
+const MAX_LINE_BYTES: usize = 4096;
+// how long the host waits on a player who isn't reading what it sends
+// before giving up on them, so they can't hold up the clock
+const WRITE_TIMEOUT: Duration = Duration::from_secs(2);
+
+fn send(stream: &mut TcpStream, message: &Message) -> io::Result<()> {
+    protocol::write_message(stream, message)
+}
+
+// the first line from a new connection, if it's a message and it all
+// arrives within `timeout`. a trickle of bytes doesn't buy more time
+fn read_hello(
+    reader: &mut BufReader<TcpStream>,
+    stream: &TcpStream,
+    timeout: Duration,
+) -> Option<Message> {
+    let deadline = Instant::now() + timeout;
+    let mut line = vec![];
+    loop {
+        let left = deadline.saturating_duration_since(Instant::now());
+        if left == Duration::from_secs(0) || line.len() > MAX_LINE_BYTES {
+            return None;
+        }
+        stream.set_read_timeout(Some(left)).ok()?;
+        let (done, used) = {
+            let available = reader.fill_buf().ok()?;
+            if available.is_empty() {
+                return None;
+            }
+            match available.iter().position(|&b| b == b'\n') {
+                Some(end) => (true, &available[..=end]),
+                None => (false, available),
+            }
+        };
+        line.extend_from_slice(used);
+        let used = used.len();
+        reader.consume(used);
+        if done {
+            break;
+        }
+    }
+    stream.set_read_timeout(None).ok()?;
+    protocol::decode(str::from_utf8(&line).ok()?).ok()
+}
+
+// a connected player, as the host sees them
+struct Seat {
+    stream: TcpStream,
+    player: Player,
+    // their round, while one is being played
+    game: Option<Game>,
+    // once they've hung up, or been hung up on, nothing more is sent
+    gone: bool,
+}
+
+impl Seat {
+    // a player who can't be written to is hung up on.
+    // their round still counts with what they found so far
+    fn send(&mut self, message: &Message) {
+        if !self.gone && send(&mut self.stream, message).is_err() {
+            self.hang_up();
+        }
+    }
+
+    fn hang_up(&mut self) {
+        self.gone = true;
+        let _ = self.stream.shutdown(Shutdown::Both);
+    }
+}
+
+pub struct Server {
+    listener: TcpListener,
+    dictionary: Option<Arc<Dictionary>>,
+    rules: Rules,
+    seats: Vec<Seat>,
+    hello_timeout: Duration,
+    // messages from every player's reader thread, tagged with their seat
+    messages: Receiver<(usize, Option<Message>)>,
+    sender: Sender<(usize, Option<Message>)>,
+}
+
+impl Server {
+    pub fn bind<A: ToSocketAddrs>(addr: A, dictionary: Option<Dictionary>) -> io::Result<Server> {
+        let (sender, messages) = channel();
+        Ok(Server {
+            listener: TcpListener::bind(addr)?,
+            dictionary: dictionary.map(Arc::new),
+            rules: Rules::default(),
+            seats: vec![],
+            hello_timeout: HELLO_TIMEOUT,
+            messages,
+            sender,
+        })
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    pub fn set_rules(&mut self, rules: Rules) {
+        self.rules = rules;
+    }
+
+    // how long a new connection has to say hello before it's dropped,
+    // so someone who connects and says nothing doesn't keep others out
+    pub fn set_hello_timeout(&mut self, timeout: Duration) {
+        self.hello_timeout = timeout;
+    }
+
+    // names of everyone who has joined, in the order they joined
+    pub fn players(&self) -> Vec<&str> {
+        self.seats
+            .iter()
+            .map(|s| s.player.name.as_str())
+            .collect()
+    }
+
+    // take connections until `count` players have said hello.
+    // connections that don't start with a hello in time are dropped, and so
+    // are players speaking another version of the protocol once they're told
+    pub fn wait_for_players(&mut self, count: usize) -> io::Result<()> {
+        while self.seats.len() < count {
+            let (mut stream, _) = self.listener.accept()?;
+            let mut reader = BufReader::new(stream.try_clone()?);
+            let name = match read_hello(&mut reader, &stream, self.hello_timeout) {
+                Some(Message::Hello { version, name }) => {
+                    if version != VERSION {
+                        let message = format!(
+                            "this host speaks version {} of the protocol, not {}",
+                            VERSION, version
+                        );
+                        let _ = send(&mut stream, &Message::Error { message });
+                        continue;
+                    }
+                    self.unique_name(&name)
+                }
+                _ => continue,
+            };
+            let hello = Message::Hello {
+                version: VERSION,
+                name: name.clone(),
+            };
+            if stream.set_write_timeout(Some(WRITE_TIMEOUT)).is_err()
+                || send(&mut stream, &hello).is_err()
+            {
+                continue;
+            }
+
+            let seat = self.seats.len();
+            let sender = self.sender.clone();
+            thread::spawn(move || loop {
+                // a broken or overlong line is as good as hanging up
+                let message = read_message_limited(&mut reader, MAX_LINE_BYTES).unwrap_or(None);
+                let done = message.is_none();
+                if sender.send((seat, message)).is_err() || done {
+                    return;
+                }
+            });
+            self.seats.push(Seat {
+                stream,
+                player: Player::new(name),
+                game: None,
+                gone: false,
+            });
+        }
+        Ok(())
+    }
+
+    // `name`, or `name 2`, `name 3`... when it's taken. blank names
+    // become "Player N" like in hot-seat games
+    fn unique_name(&self, name: &str) -> String {
+        let name = name.trim();
+        let base = if name.is_empty() {
+            format!("Player {}", self.seats.len() + 1)
+        } else {
+            name.to_string()
+        };
+        let taken = |candidate: &str| {
+            self.seats
+                .iter()
+                .any(|s| s.player.name.eq_ignore_ascii_case(candidate))
+        };
+        let mut candidate = base.clone();
+        let mut n = 2;
+        while taken(&candidate) {
+            candidate = format!("{} {}", base, n);
+            n += 1;
+        }
+        candidate
+    }
+
+    // everyone plays `board` against the same clock. every guess is checked
+    // here, and the results are sent to everyone once time is up
+    pub fn play_round(&mut self, board: Board, minutes: f64) -> io::Result<Cancellation> {
+        let mut timer = Timer::new();
+        timer.set_play_time(minutes);
+        let announce = Message::Board {
+            board: board.code(),
+            seed: board.seed(),
+            seconds: timer.get_remaining_time().max(0) as u64,
+        };
+        for seat in self.seats.iter_mut() {
+            let mut game = Game::new(board.clone(), seat.player.clone(), self.dictionary.clone());
+            game.rules = self.rules.clone();
+            seat.game = Some(game);
+        }
+        self.broadcast(&announce);
+        self.broadcast(&Message::Start);
+        timer.start();
+
+        let mut last_tick = timer.get_remaining_time();
+        while !timer.is_time_up() {
+            let left = timer.get_remaining_time();
+            if left != last_tick && left % TICK_SECS == 0 && left > 0 {
+                self.broadcast(&Message::Tick {
+                    seconds_left: left as u64,
+                });
+            }
+            last_tick = left;
+            let (seat, message) = match self.messages.recv_timeout(POLL) {
+                Ok(received) => received,
+                Err(RecvTimeoutError::Timeout) => continue,
+                Err(RecvTimeoutError::Disconnected) => break,
+            };
+            let seat = &mut self.seats[seat];
+            let word = match message {
+                Some(Message::Guess { word }) => word,
+                None => {
+                    seat.hang_up();
+                    continue;
+                }
+                // players can't do anything else mid-round
+                _ => continue,
+            };
+            let game = match seat.game {
+                Some(ref mut game) => game,
+                None => continue,
+            };
+            let result = game.add_guess_at(word.clone(), timer.elapsed_secs());
+            let verdict = Message::verdict(word, result, game.guesses.score());
+            seat.send(&verdict);
+        }
+
+        let games: Vec<Game> = self.seats.iter_mut().filter_map(|s| s.game.take()).collect();
+        let cancellation = Cancellation::new(&games);
+        self.broadcast(&Message::End);
+        self.broadcast(&Message::results(&cancellation));
+        Ok(cancellation)
+    }
+
+    // players who have left are skipped
+    fn broadcast(&mut self, message: &Message) {
+        for seat in self.seats.iter_mut() {
+            seat.send(message);
+        }
+    }
+}
+
+// what a player's terminal waits on: their typing or the host
+enum Event {
+    Line(String),
+    InputClosed,
+    Host(Option<Message>),
+}
+
+pub struct Client {
+    stream: TcpStream,
+    reader: BufReader<TcpStream>,
+    name: String,
+}
+
+impl Client {
+    // say hello and wait for the host to say it back
+    pub fn connect<A: ToSocketAddrs>(addr: A, name: &str) -> io::Result<Client> {
+        let mut stream = TcpStream::connect(addr)?;
+        send(
+            &mut stream,
+            &Message::Hello {
+                version: VERSION,
+                name: name.to_string(),
+            },
+        )?;
+        let mut reader = BufReader::new(stream.try_clone()?);
+        match receive(&mut reader)? {
+            Some(Message::Hello { name, .. }) => Ok(Client {
+                stream,
+                reader,
+                name,
+            }),
+            Some(Message::Error { message }) => Err(io::Error::other(message)),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "the host didn't say hello",
+            )),
+        }
+    }
+
+    // the name the host knows us by, which may not be the one asked for
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    // play the round the host deals: lines from `input` are guesses,
+    // verdicts and results are written to `output`
+    pub fn play<I, W>(self, input: I, output: &mut Console<W>) -> io::Result<()>
+    where
+        I: BufRead + Send + 'static,
+        W: Write,
+    {
+        let (sender, events) = channel();
+        let lines = sender.clone();
+        thread::spawn(move || {
+            for line in input.lines() {
+                match line {
+                    Ok(line) => {
+                        if lines.send(Event::Line(line)).is_err() {
+                            return;
+                        }
+                    }
+                    Err(_) => break,
+                }
+            }
+            let _ = lines.send(Event::InputClosed);
+        });
+        let mut reader = self.reader;
+        thread::spawn(move || loop {
+            let message = receive(&mut reader).unwrap_or(None);
+            let done = message.is_none();
+            if sender.send(Event::Host(message)).is_err() || done {
+                return;
+            }
+        });
+
+        let mut stream = self.stream;
+        writeln!(output, "Joined as {}. Waiting for the host to start...", self.name)?;
+        // guesses typed before the start are sent once the clock runs
+        let mut early = vec![];
+        let mut started = false;
+        let mut ended = false;
+        loop {
+            let event = match events.recv() {
+                Ok(event) => event,
+                Err(_) => return Ok(()),
+            };
+            match event {
+                Event::Line(line) => {
+                    let word = line.trim().to_string();
+                    if word.is_empty() || ended {
+                        continue;
+                    }
+                    if started {
+                        send(&mut stream, &Message::Guess { word })?;
+                    } else {
+                        early.push(word);
+                    }
+                }
+                Event::InputClosed => (),
+                Event::Host(None) => {
+                    writeln!(output, "The host has gone away")?;
+                    return Ok(());
+                }
+                Event::Host(Some(message)) => match message {
+                    Message::Board { board, seconds, .. } => {
+                        let board = Board::from_code(&board)
+                            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+                        writeln!(output, "{}", board)?;
+                        writeln!(output, "Enter as many words as possible in {} seconds!", seconds)?;
+                    }
+                    Message::Start => {
+                        started = true;
+                        writeln!(output, "Go!")?;
+                        for word in early.drain(..) {
+                            send(&mut stream, &Message::Guess { word })?;
+                        }
+                    }
+                    Message::Tick { seconds_left } => {
+                        writeln!(output, "{} seconds left", seconds_left)?
+                    }
+                    Message::Verdict {
+                        word,
+                        points,
+                        rejection,
+                        reason,
+                        score,
+                    } => match reason.or_else(|| rejection.map(|r| r.to_string())) {
+                        None => writeln!(output, "\"{}\" +{} (score {})", word, points, score)?,
+                        Some(reason) => {
+                            writeln!(output, "\"{}\" doesn't count: {}", word, reason)?
+                        }
+                    },
+                    Message::End => {
+                        ended = true;
+                        writeln!(output, "Time's up!")?;
+                    }
+                    Message::Results {
+                        players,
+                        words,
+                        scores,
+                    } => {
+                        let cancellation = Cancellation {
+                            players,
+                            words,
+                            scores,
+                        };
+                        writeln!(output, "\n{}", cancellation)?;
+                        return Ok(());
+                    }
+                    Message::Error { message } => {
+                        writeln!(output, "The host says: {}", message)?;
+                        return Ok(());
+                    }
+                    // the hello was answered in `connect`, guesses go the other way
+                    Message::Hello { .. } | Message::Guess { .. } => (),
+                },
+            }
+        }
+    }
+}
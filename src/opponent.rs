@@ -0,0 +1,327 @@
+//! A computer player. It knows some of the words on the board, the more
+//! common ones first going by a word frequency list, and "finds" them one
+//! at a time as the round goes on.
+//! How many it knows, how fast it is and whether it goes for short or long
+//! words depends on the difficulty. Any other `Strategy` can take its seat
+
+use std::collections::VecDeque;
+use std::fmt::{self, Display, Formatter};
+use std::sync::Arc;
+
+use rand::{Rng, SeedableRng, XorShiftRng};
+
+use board::Board;
+use dictionary::Dictionary;
+use frequency::Frequencies;
+use game::{Game, Player};
+use solver;
+use strategy::Strategy;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Medium,
+    Hard,
+}
+
+impl Difficulty {
+    pub fn from_name(name: &str) -> Option<Difficulty> {
+        match name {
+            "easy" => Some(Difficulty::Easy),
+            "medium" => Some(Difficulty::Medium),
+            "hard" => Some(Difficulty::Hard),
+            _ => None,
+        }
+    }
+
+    pub fn skill(self) -> Skill {
+        match self {
+            Difficulty::Easy => Skill {
+                vocabulary: 0.3,
+                words_per_minute: 4.0,
+                long_words: -1.0,
+            },
+            Difficulty::Medium => Skill {
+                vocabulary: 0.6,
+                words_per_minute: 8.0,
+                long_words: 0.0,
+            },
+            Difficulty::Hard => Skill {
+                vocabulary: 0.95,
+                words_per_minute: 15.0,
+                long_words: 1.5,
+            },
+        }
+    }
+}
+
+impl Display for Difficulty {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match *self {
+            Difficulty::Easy => write!(f, "easy"),
+            Difficulty::Medium => write!(f, "medium"),
+            Difficulty::Hard => write!(f, "hard"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Skill {
+    // share of the board's words it knows, from 0 to 1, commonest first
+    // (see `by_frequency`)
+    pub vocabulary: f64,
+    // how many words it finds a minute, on average
+    pub words_per_minute: f64,
+    // how much it goes for long words: below 0 it prefers short ones,
+    // 0 doesn't care, above 0 prefers long ones
+    pub long_words: f64,
+}
+
+// how unusual a word looks, going by its letters: the sum of their
+// Scrabble® tile values, which are low for common letters. only used for
+// words the frequency list doesn't have, or when there's no list at all
+pub fn rarity(word: &str) -> u32 {
+    word.chars()
+        .map(|c| match c.to_ascii_uppercase() {
+            'A' | 'E' | 'I' | 'L' | 'N' | 'O' | 'R' | 'S' | 'T' | 'U' => 1,
+            'D' | 'G' => 2,
+            'B' | 'C' | 'M' | 'P' => 3,
+            'F' | 'H' | 'V' | 'W' | 'Y' => 4,
+            'K' => 5,
+            'J' | 'X' => 8,
+            'Q' | 'Z' => 10,
+            _ => 0,
+        })
+        .sum()
+}
+
+// `words` commonest first: the ones in `frequencies` in the list's order,
+// then the rest by how rare their letters are
+pub fn by_frequency(words: &mut [&String], frequencies: Option<&Frequencies>) {
+    words.sort_by_key(|w| {
+        let rank = frequencies.and_then(|f| f.rank(w)).unwrap_or(usize::MAX);
+        (rank, rarity(w), w.len())
+    });
+}
+
+// the words a player with `skill` finds out of `solutions` in a round of
+// `seconds`, each with the second it's found at, in order
+pub fn plan<R: Rng>(
+    solutions: &[String],
+    frequencies: Option<&Frequencies>,
+    skill: &Skill,
+    seconds: u64,
+    rng: &mut R,
+) -> Vec<(u64, String)> {
+    let mut known: Vec<&String> = solutions.iter().collect();
+    by_frequency(&mut known, frequencies);
+    let size = (known.len() as f64 * skill.vocabulary.clamp(0.0, 1.0)).ceil() as usize;
+    known.truncate(size);
+
+    // a weighted shuffle: each word is drawn with a chance
+    // in proportion to its length raised to `long_words`
+    let mut keyed: Vec<(f64, &String)> = known
+        .into_iter()
+        .map(|w| {
+            let weight = (w.chars().count() as f64).powf(skill.long_words);
+            (rng.gen::<f64>().powf(1.0 / weight), w)
+        })
+        .collect();
+    keyed.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(::std::cmp::Ordering::Equal));
+
+    // words come at an uneven pace, from half to one and a half times the average gap
+    let gap = 60.0 / skill.words_per_minute.max(0.1);
+    let mut at = 0.0;
+    let mut planned = vec![];
+    for (_, word) in keyed {
+        at += gap * (0.5 + rng.gen::<f64>());
+        if at >= seconds as f64 {
+            break;
+        }
+        planned.push((at as u64, word.to_lowercase()));
+    }
+    planned
+}
+
+// the built-in computer player, as a `Strategy`
+pub struct Computer {
+    difficulty: Difficulty,
+    dictionary: Arc<Dictionary>,
+    frequencies: Option<Arc<Frequencies>>,
+    rng: XorShiftRng,
+}
+
+impl Computer {
+    // the same seed plays the same boards the same way
+    pub fn new(difficulty: Difficulty, dictionary: Arc<Dictionary>, seed: u64) -> Computer {
+        // like `Board::from_seed`, the constants keep the seed from being all zeros
+        let rng = XorShiftRng::from_seed([
+            seed as u32,
+            (seed >> 32) as u32,
+            0x85EB_CA6B,
+            0xC2B2_AE35,
+        ]);
+        Computer {
+            difficulty,
+            dictionary,
+            frequencies: None,
+            rng,
+        }
+    }
+
+    // which words it knows first, without a list it goes by their letters
+    pub fn set_frequencies(&mut self, frequencies: Arc<Frequencies>) {
+        self.frequencies = Some(frequencies);
+    }
+}
+
+impl Strategy for Computer {
+    fn name(&self) -> String {
+        format!("Computer ({})", self.difficulty)
+    }
+
+    fn play(&mut self, board: &Board, seconds: u64) -> Vec<(u64, String)> {
+        let solutions = solver::solve(board, &self.dictionary);
+        let frequencies = self.frequencies.as_deref();
+        plan(&solutions, frequencies, &self.difficulty.skill(), seconds, &mut self.rng)
+    }
+}
+
+// a computer player's round, played alongside someone else's
+pub struct Opponent {
+    pub game: Game,
+    // words still to be found, soonest first
+    plan: VecDeque<(u64, String)>,
+}
+
+impl Opponent {
+    // `strategy` plans its round on `board` up front,
+    // its guesses are checked against `dictionary` as they come due
+    pub fn new(
+        board: Board,
+        dictionary: Arc<Dictionary>,
+        strategy: &mut dyn Strategy,
+        seconds: u64,
+    ) -> Opponent {
+        let mut planned = strategy.play(&board, seconds);
+        planned.sort_by_key(|&(at, _)| at);
+        let player = Player::new(strategy.name());
+        Opponent {
+            game: Game::new(board, player, Some(dictionary)),
+            plan: planned.into_iter().collect(),
+        }
+    }
+
+    // find every word planned for up to `secs` into the round,
+    // returns how many that was
+    pub fn catch_up(&mut self, secs: u64) -> usize {
+        let mut found = 0;
+        while self.plan.front().is_some_and(|(at, _)| *at <= secs) {
+            if let Some((at, word)) = self.plan.pop_front() {
+                if self.game.add_guess_at(word, at).is_ok() {
+                    found += 1;
+                }
+            }
+        }
+        found
+    }
+
+    // words found so far
+    pub fn found(&self) -> usize {
+        self.game.guesses.valid().len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rng() -> XorShiftRng {
+        XorShiftRng::from_seed([1, 2, 3, 4])
+    }
+
+    fn words(list: &[&str]) -> Vec<String> {
+        list.iter().map(|w| w.to_uppercase()).collect()
+    }
+
+    #[test]
+    fn common_words_are_known_first() {
+        assert!(rarity("cat") < rarity("quiz"));
+        let solutions = words(&["quiz", "jazz", "cat", "rat", "tea", "zax"]);
+        let skill = Skill {
+            vocabulary: 0.5,
+            words_per_minute: 600.0,
+            long_words: 0.0,
+        };
+        let mut planned: Vec<String> = plan(&solutions, None, &skill, 60, &mut rng())
+            .into_iter()
+            .map(|(_, w)| w)
+            .collect();
+        planned.sort();
+        assert_eq!(planned, vec!["cat", "rat", "tea"]);
+    }
+
+    #[test]
+    fn the_frequency_list_comes_first() {
+        let solutions = words(&["quiz", "jazz", "cat", "rat", "tea", "zax"]);
+        let frequencies = Frequencies::from_words(vec!["the", "jazz", "quiz", "tea"]);
+        let skill = Skill {
+            vocabulary: 0.5,
+            words_per_minute: 600.0,
+            long_words: 0.0,
+        };
+        let mut planned: Vec<String> = plan(&solutions, Some(&frequencies), &skill, 60, &mut rng())
+            .into_iter()
+            .map(|(_, w)| w)
+            .collect();
+        planned.sort();
+        assert_eq!(planned, vec!["jazz", "quiz", "tea"]);
+
+        // words it doesn't list come after, by their letters
+        let mut known: Vec<&String> = solutions.iter().collect();
+        by_frequency(&mut known, Some(&frequencies));
+        assert_eq!(known, vec!["JAZZ", "QUIZ", "TEA", "RAT", "CAT", "ZAX"]);
+    }
+
+    #[test]
+    fn words_come_in_order_at_about_the_rate() {
+        let solutions: Vec<String> = (0..100).map(|i| format!("W{:03}", i)).collect();
+        let skill = Skill {
+            vocabulary: 1.0,
+            words_per_minute: 10.0,
+            long_words: 0.0,
+        };
+        let planned = plan(&solutions, None, &skill, 180, &mut rng());
+        // 30 on average in three minutes
+        assert!((20..=45).contains(&planned.len()), "{}", planned.len());
+        assert!(planned.windows(2).all(|w| w[0].0 <= w[1].0));
+        assert!(planned.iter().all(|&(at, _)| at < 180));
+    }
+
+    #[test]
+    fn long_word_lovers_start_long() {
+        let solutions = words(&["at", "to", "it", "an", "no", "on", "strains", "trains"]);
+        let mut skill = Skill {
+            vocabulary: 1.0,
+            words_per_minute: 600.0,
+            long_words: 8.0,
+        };
+        let first = |skill: &Skill| plan(&solutions, None, skill, 60, &mut rng())[0].1.len();
+        assert!(first(&skill) >= 6);
+        skill.long_words = -8.0;
+        assert_eq!(first(&skill), 2);
+    }
+
+    #[test]
+    fn opponent_finds_words_as_time_goes_by() {
+        let board = Board::cats_and_dog();
+        let dictionary = Arc::new(Dictionary::from_words(vec!["cat", "cats", "dog"]));
+        let mut computer = Computer::new(Difficulty::Hard, dictionary.clone(), 7);
+        let mut opponent = Opponent::new(board, dictionary, &mut computer, 600);
+        assert_eq!(opponent.catch_up(0), 0);
+        assert_eq!(opponent.catch_up(600), 3);
+        assert_eq!(opponent.found(), 3);
+        assert_eq!(opponent.game.player.name, "Computer (hard)");
+    }
+}
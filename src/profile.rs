@@ -0,0 +1,202 @@
+//! Named players kept between launches, each with their preferences
+//! and lifetime statistics. All profiles live in one `profiles.json`
+//! in the same directory as the history
+
+use std::fmt::{self, Display, Formatter};
+use std::fs::File;
+use std::io::{self, BufReader};
+use std::path::{Path, PathBuf};
+
+use serde_json;
+
+use game::Game;
+use history;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Theme {
+    // colors wherever the terminal has them
+    #[default]
+    Color,
+    // never any colors
+    Mono,
+}
+
+impl Theme {
+    pub fn from_name(name: &str) -> Option<Theme> {
+        match name {
+            "color" => Some(Theme::Color),
+            "mono" => Some(Theme::Mono),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Theme {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match *self {
+            Theme::Color => write!(f, "color"),
+            Theme::Mono => write!(f, "mono"),
+        }
+    }
+}
+
+// None means "use the game's default"
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Preferences {
+    pub minutes: Option<f64>,
+    // (rows, cols)
+    pub board_size: Option<(usize, usize)>,
+    pub dictionary: Option<PathBuf>,
+    pub theme: Theme,
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LifetimeStats {
+    pub games: usize,
+    pub total_score: usize,
+    pub best_score: usize,
+    pub longest_word: String,
+}
+
+impl LifetimeStats {
+    pub fn record(&mut self, game: &Game) {
+        let score = game.guesses.score();
+        self.games += 1;
+        self.total_score += score;
+        self.best_score = self.best_score.max(score);
+        for guess in game.guesses.valid() {
+            if guess.word().chars().count() > self.longest_word.chars().count() {
+                self.longest_word = guess.word().to_string();
+            }
+        }
+    }
+
+    pub fn average(&self) -> f64 {
+        if self.games == 0 {
+            return 0.0;
+        }
+        self.total_score as f64 / self.games as f64
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Profile {
+    pub name: String,
+    #[serde(default)]
+    pub preferences: Preferences,
+    #[serde(default)]
+    pub stats: LifetimeStats,
+}
+
+impl Profile {
+    pub fn new(name: &str) -> Profile {
+        Profile {
+            name: name.to_string(),
+            preferences: Preferences::default(),
+            stats: LifetimeStats::default(),
+        }
+    }
+}
+
+pub struct ProfileStore {
+    path: PathBuf,
+}
+
+impl ProfileStore {
+    pub fn new<P: Into<PathBuf>>(path: P) -> ProfileStore {
+        ProfileStore { path: path.into() }
+    }
+
+    // profiles.json in the data directory
+    pub fn open_default() -> Option<ProfileStore> {
+        history::data_dir().map(|dir| ProfileStore::new(dir.join("profiles.json")))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    // every profile, in the order they were made. no file yet means none
+    pub fn load(&self) -> io::Result<Vec<Profile>> {
+        let file = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(e) => return Err(e),
+        };
+        serde_json::from_reader(BufReader::new(file))
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    // the profile called `name`, ignoring case
+    pub fn find(&self, name: &str) -> io::Result<Option<Profile>> {
+        let name = name.to_lowercase();
+        Ok(self.load()?
+            .into_iter()
+            .find(|p| p.name.to_lowercase() == name))
+    }
+
+    // add `profile`, or replace the one with the same name
+    pub fn save(&self, profile: &Profile) -> io::Result<()> {
+        let mut profiles = self.load()?;
+        let name = profile.name.to_lowercase();
+        match profiles.iter().position(|p| p.name.to_lowercase() == name) {
+            Some(i) => profiles[i] = profile.clone(),
+            None => profiles.push(profile.clone()),
+        }
+
+        // a crash halfway through never leaves a broken profiles.json
+        history::write_atomically(&self.path, |file| {
+            serde_json::to_writer_pretty(file, &profiles).map_err(io::Error::other)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use board::Board;
+    use game::Player;
+    use std::env;
+    use std::fs;
+    use std::process;
+
+    #[test]
+    fn lifetime_stats_add_up() {
+        let board = Board::cats_and_dog();
+        let mut stats = LifetimeStats::default();
+
+        let mut game = Game::new(board.clone(), Player::new(String::from("p")), None);
+        let _ = game.add_guess(String::from("cats"));
+        let _ = game.add_guess(String::from("dog"));
+        stats.record(&game);
+        stats.record(&Game::new(board, Player::new(String::from("p")), None));
+
+        assert_eq!(stats.games, 2);
+        assert_eq!(stats.best_score, 2);
+        assert_eq!(stats.average(), 1.0);
+        assert_eq!(stats.longest_word, "cats");
+    }
+
+    #[test]
+    fn store_saves_and_replaces() {
+        let dir = env::temp_dir().join(format!("boggle-profiles-{}", process::id()));
+        let store = ProfileStore::new(dir.join("profiles.json"));
+        assert!(store.load().unwrap().is_empty());
+
+        let mut eric = Profile::new("Eric");
+        store.save(&eric).unwrap();
+        store.save(&Profile::new("ana")).unwrap();
+        eric.preferences.theme = Theme::Mono;
+        eric.preferences.board_size = Some((5, 5));
+        store.save(&eric).unwrap();
+
+        let names: Vec<String> = store.load().unwrap().into_iter().map(|p| p.name).collect();
+        assert_eq!(names, vec!["Eric", "ana"]);
+        assert_eq!(store.find("eric").unwrap(), Some(eric));
+        assert_eq!(store.find("bob").unwrap(), None);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
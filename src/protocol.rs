@@ -0,0 +1,284 @@
+//! The messages `boggle host` and its players send each other.
+//! Every message is one JSON object on its own line, with a "type" field
+//! saying which message it is. PROTOCOL.md describes them for anyone
+//! writing their own client or bot
+
+use std::error;
+use std::fmt::{self, Display, Formatter};
+use std::io::{self, BufRead, Read, Write};
+use std::str;
+
+use serde_json;
+
+use multiplayer::Cancellation;
+use rejection::Rejection;
+
+// bumped whenever a message changes in a way older peers can't read
+pub const VERSION: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Message {
+    // both ways, first thing after connecting. the host answers with
+    // the name the player will go by, which is made unique
+    Hello { version: u32, name: String },
+    // host to players: the board for the round, see `Board::code`
+    Board {
+        board: String,
+        seed: Option<u64>,
+        seconds: u64,
+    },
+    // host to players: the clock is running
+    Start,
+    // host to players, every so often: what the host's clock says
+    Tick { seconds_left: u64 },
+    // player to host
+    Guess { word: String },
+    // host to the player who guessed: what the word was worth,
+    // why it didn't count if it didn't, and their score so far
+    Verdict {
+        word: String,
+        points: usize,
+        rejection: Option<Rejection>,
+        reason: Option<String>,
+        score: usize,
+    },
+    // host to players: time is up
+    End,
+    // host to players: the round once shared words are cancelled,
+    // laid out like `Cancellation`
+    Results {
+        players: Vec<String>,
+        words: Vec<(String, Vec<usize>)>,
+        scores: Vec<usize>,
+    },
+    // host to a player, just before hanging up on them
+    Error { message: String },
+}
+
+impl Message {
+    // the answer to a guess of `word`, given what `Game::add_guess` said
+    // and the player's score after it
+    pub fn verdict(word: String, result: Result<usize, Rejection>, score: usize) -> Message {
+        let (points, rejection) = match result {
+            Ok(points) => (points, None),
+            Err(rejection) => (0, Some(rejection)),
+        };
+        Message::Verdict {
+            word,
+            points,
+            reason: rejection.as_ref().map(|r| r.to_string()),
+            rejection,
+            score,
+        }
+    }
+
+    pub fn results(cancellation: &Cancellation) -> Message {
+        Message::Results {
+            players: cancellation.players.clone(),
+            words: cancellation.words.clone(),
+            scores: cancellation.scores.clone(),
+        }
+    }
+}
+
+// a line that isn't a message this version knows
+#[derive(Debug)]
+pub struct DecodeError {
+    pub line: String,
+    pub reason: String,
+}
+
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "not a message ({}): {}", self.reason, self.line.trim_end())
+    }
+}
+
+impl error::Error for DecodeError {}
+
+// the message as one line of JSON, without the newline
+pub fn encode(message: &Message) -> String {
+    // every field is a plain string, number or list, so this can't fail
+    serde_json::to_string(message).unwrap_or_default()
+}
+
+pub fn decode(line: &str) -> Result<Message, DecodeError> {
+    serde_json::from_str(line).map_err(|e| DecodeError {
+        line: line.to_string(),
+        reason: e.to_string(),
+    })
+}
+
+pub fn write_message<W: Write>(out: &mut W, message: &Message) -> io::Result<()> {
+    let mut line = encode(message);
+    line.push('\n');
+    out.write_all(line.as_bytes())?;
+    out.flush()
+}
+
+// the next message, or None once the other end hangs up.
+// lines that aren't messages (e.g. from a newer version) are skipped
+pub fn read_message<R: BufRead>(reader: &mut R) -> io::Result<Option<Message>> {
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        if let Ok(message) = decode(&line) {
+            return Ok(Some(message));
+        }
+    }
+}
+
+// like `read_message`, but a line longer than `max` bytes is an error
+// instead of something to keep reading into memory
+pub fn read_message_limited<R: BufRead>(
+    reader: &mut R,
+    max: usize,
+) -> io::Result<Option<Message>> {
+    loop {
+        let mut line = vec![];
+        if reader.by_ref().take(max as u64 + 1).read_until(b'\n', &mut line)? == 0 {
+            return Ok(None);
+        }
+        if line.len() > max {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("a line longer than {} bytes", max),
+            ));
+        }
+        let message = str::from_utf8(&line).ok().and_then(|line| decode(line).ok());
+        if message.is_some() {
+            return Ok(message);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(message: Message, line: &str) {
+        assert_eq!(encode(&message), line);
+        assert_eq!(decode(line).unwrap(), message);
+    }
+
+    #[test]
+    fn every_message_round_trips() {
+        round_trip(
+            Message::Hello {
+                version: 1,
+                name: String::from("eric"),
+            },
+            r#"{"type":"hello","version":1,"name":"eric"}"#,
+        );
+        round_trip(
+            Message::Board {
+                board: String::from("2x2:ABCD"),
+                seed: Some(42),
+                seconds: 180,
+            },
+            r#"{"type":"board","board":"2x2:ABCD","seed":42,"seconds":180}"#,
+        );
+        round_trip(Message::Start, r#"{"type":"start"}"#);
+        round_trip(
+            Message::Tick { seconds_left: 60 },
+            r#"{"type":"tick","seconds_left":60}"#,
+        );
+        round_trip(
+            Message::Guess {
+                word: String::from("cat"),
+            },
+            r#"{"type":"guess","word":"cat"}"#,
+        );
+        round_trip(
+            Message::Verdict {
+                word: String::from("cat"),
+                points: 1,
+                rejection: None,
+                reason: None,
+                score: 3,
+            },
+            r#"{"type":"verdict","word":"cat","points":1,"rejection":null,"reason":null,"score":3}"#,
+        );
+        round_trip(Message::End, r#"{"type":"end"}"#);
+        round_trip(
+            Message::Results {
+                players: vec![String::from("ana"), String::from("eric")],
+                words: vec![(String::from("cat"), vec![0, 1]), (String::from("dog"), vec![1])],
+                scores: vec![0, 1],
+            },
+            r#"{"type":"results","players":["ana","eric"],"words":[["cat",[0,1]],["dog",[1]]],"scores":[0,1]}"#,
+        );
+        round_trip(
+            Message::Error {
+                message: String::from("bye"),
+            },
+            r#"{"type":"error","message":"bye"}"#,
+        );
+    }
+
+    #[test]
+    fn rejections_round_trip() {
+        let rejections = vec![
+            (
+                Rejection::LetterNotOnBoard { letter: 'Z' },
+                r#"{"kind":"letter_not_on_board","letter":"Z"}"#,
+            ),
+            (
+                Rejection::NotAdjacent {
+                    position: 2,
+                    letter: 'D',
+                },
+                r#"{"kind":"not_adjacent","position":2,"letter":"D"}"#,
+            ),
+            (
+                Rejection::ReusesTile { row: 0, col: 2 },
+                r#"{"kind":"reuses_tile","row":0,"col":2}"#,
+            ),
+            (Rejection::NotInDictionary, r#"{"kind":"not_in_dictionary"}"#),
+            (
+                Rejection::TooShort { min: 4 },
+                r#"{"kind":"too_short","min":4}"#,
+            ),
+            (Rejection::Duplicate, r#"{"kind":"duplicate"}"#),
+        ];
+        for (rejection, json) in rejections {
+            let message = Message::Verdict {
+                word: String::from("x"),
+                points: 0,
+                reason: Some(rejection.to_string()),
+                rejection: Some(rejection),
+                score: 0,
+            };
+            let line = encode(&message);
+            assert!(line.contains(json), "{} has no {}", line, json);
+            assert_eq!(decode(&line).unwrap(), message);
+        }
+    }
+
+    #[test]
+    fn reading_skips_what_it_does_not_know() {
+        let input = "{\"type\":\"shout\",\"at\":\"everyone\"}\nnot json\n{\"type\":\"start\"}\n";
+        let mut reader = input.as_bytes();
+        assert_eq!(read_message(&mut reader).unwrap(), Some(Message::Start));
+        assert_eq!(read_message(&mut reader).unwrap(), None);
+
+        let input = b"{\"type\":\"start\"}\n\xff\n{\"type\":\"guess\",\"word\":\"catsssss\"}\n";
+        let mut reader = &input[..];
+        assert_eq!(read_message_limited(&mut reader, 20).unwrap(), Some(Message::Start));
+        let error = read_message_limited(&mut reader, 20).unwrap_err();
+        assert_eq!(error.to_string(), "a line longer than 20 bytes");
+
+        let mut out = vec![];
+        write_message(&mut out, &Message::End).unwrap();
+        assert_eq!(out, b"{\"type\":\"end\"}\n");
+    }
+
+    #[test]
+    fn decode_says_what_went_wrong() {
+        let error = decode("{\"type\":\"guess\"}").unwrap_err();
+        assert!(error.to_string().starts_with("not a message (missing field `word`"));
+    }
+}
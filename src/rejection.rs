@@ -0,0 +1,189 @@
+//! Why a guess didn't count
+
+use std::fmt::{self, Display, Formatter};
+
+use board::Board;
+
+// written out as an object with a "kind", e.g. {"kind":"duplicate"}.
+// like everything else on the board, positions count from 0
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Rejection {
+    LetterNotOnBoard { letter: char },
+    // every letter is on the board, but the letter at `position`
+    // never touches the letters before it
+    NotAdjacent { position: usize, letter: char },
+    // the only way on is back through the tile at (row, col)
+    ReusesTile { row: usize, col: usize },
+    NotInDictionary,
+    TooShort { min: usize },
+    // the word was already found
+    Duplicate,
+}
+
+impl Display for Rejection {
+    // positions are shown 1-based, the way a player counts them
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match *self {
+            Rejection::LetterNotOnBoard { letter } => {
+                write!(f, "the letter {} is not on the board", letter)
+            }
+            Rejection::NotAdjacent { position, letter } => write!(
+                f,
+                "letter {} ({}) doesn't touch the letters before it",
+                position + 1,
+                letter
+            ),
+            Rejection::ReusesTile { row, col } => write!(
+                f,
+                "it would use the tile at row {}, column {} twice",
+                row + 1,
+                col + 1
+            ),
+            Rejection::NotInDictionary => write!(f, "it's not in the dictionary"),
+            Rejection::TooShort { min } => write!(f, "words need at least {} letters", min),
+            Rejection::Duplicate => write!(f, "you already found it"),
+        }
+    }
+}
+
+// why `word` can't be traced on `board`, or None if it can
+pub fn diagnose(board: &Board, word: &str) -> Option<Rejection> {
+    let letters: Vec<char> = word.to_uppercase().chars().collect();
+    if letters.is_empty() {
+        return Some(Rejection::TooShort { min: 1 });
+    }
+    let mut collection = vec![];
+    for &letter in letters.iter() {
+        let instances = board.get_all_instances_of(letter);
+        if instances.is_empty() {
+            return Some(Rejection::LetterNotOnBoard { letter });
+        }
+        collection.push(instances);
+    }
+    if board.has_path(word) {
+        return None;
+    }
+
+    // walk every partial path, remembering how far the best one got
+    // and whether it was stopped only by a tile it had already used
+    let mut furthest = Furthest {
+        depth: 0,
+        reused: None,
+    };
+    for &start in collection[0].iter() {
+        walk(board, &collection, &mut vec![start], &mut furthest);
+    }
+    match furthest.reused {
+        Some(index) => {
+            let piece = board.piece(index)?;
+            Some(Rejection::ReusesTile {
+                row: piece.row(),
+                col: piece.col(),
+            })
+        }
+        None => Some(Rejection::NotAdjacent {
+            position: furthest.depth,
+            letter: letters[furthest.depth],
+        }),
+    }
+}
+
+struct Furthest {
+    // letters matched by the longest partial path
+    depth: usize,
+    // a tile that would have let that path go one letter further
+    reused: Option<usize>,
+}
+
+fn walk(board: &Board, collection: &[Vec<usize>], path: &mut Vec<usize>, furthest: &mut Furthest) {
+    let depth = path.len();
+    if depth > furthest.depth {
+        furthest.depth = depth;
+        furthest.reused = None;
+    }
+    if depth == collection.len() {
+        return;
+    }
+    let last = path[depth - 1];
+    for &next in collection[depth].iter() {
+        if !board.are_neighbors(last, next) {
+            continue;
+        }
+        if path.contains(&next) {
+            if depth == furthest.depth && furthest.reused.is_none() {
+                furthest.reused = Some(next);
+            }
+            continue;
+        }
+        path.push(next);
+        walk(board, collection, path, furthest);
+        path.pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn board() -> Board {
+        let faces: Vec<String> = "CATSXXXXDOGXXXXA".chars().map(|c| c.to_string()).collect();
+        Board::try_from_letters(4, 4, &faces).unwrap()
+    }
+
+    #[test]
+    fn playable_words_are_fine() {
+        assert_eq!(diagnose(&board(), "cats"), None);
+    }
+
+    #[test]
+    fn letter_not_on_board() {
+        assert_eq!(
+            diagnose(&board(), "zebra"),
+            Some(Rejection::LetterNotOnBoard { letter: 'Z' })
+        );
+    }
+
+    #[test]
+    fn not_adjacent() {
+        // C A T are fine, D is nowhere near T
+        assert_eq!(
+            diagnose(&board(), "catd"),
+            Some(Rejection::NotAdjacent {
+                position: 3,
+                letter: 'D',
+            })
+        );
+        // neither A reaches D
+        assert_eq!(
+            diagnose(&board(), "ad"),
+            Some(Rejection::NotAdjacent {
+                position: 1,
+                letter: 'D',
+            })
+        );
+    }
+
+    #[test]
+    fn reuses_tile() {
+        assert_eq!(
+            diagnose(&board(), "tat"),
+            Some(Rejection::ReusesTile { row: 0, col: 2 })
+        );
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            Rejection::ReusesTile { row: 0, col: 2 }.to_string(),
+            "it would use the tile at row 1, column 3 twice"
+        );
+        assert_eq!(
+            Rejection::NotAdjacent {
+                position: 3,
+                letter: 'D',
+            }.to_string(),
+            "letter 4 (D) doesn't touch the letters before it"
+        );
+    }
+}
@@ -0,0 +1,15 @@
+//! House rules for a game
+
+// missing fields are left at their defaults when reading old saves
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Rules {
+    // points taken off the score for every hint
+    pub hint_points: usize,
+    // seconds taken off the clock for every hint
+    pub hint_seconds: u64,
+    // shorter words are turned down, 0 accepts anything
+    pub min_word_length: usize,
+    // practice rounds let the player take a "did you mean" with one keystroke
+    pub practice: bool,
+}
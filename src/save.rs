@@ -0,0 +1,120 @@
+//! A round put aside with `:save`, or when the game is interrupted,
+//! to be picked up later with `boggle resume`. The clock stops while
+//! it's saved: the round carries on with the time that was left
+
+use std::fs::File;
+use std::io::{self, BufReader};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use serde_json;
+
+use board::{Board, BoardError};
+use dictionary::Dictionary;
+use game::{Game, Player};
+use history::{self, GameRecord};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedGame {
+    // the round so far, as it would go in the history. `played_at` is
+    // when it was saved and `seconds_played` how far into the round it was
+    pub game: GameRecord,
+    pub seconds_left: u64,
+}
+
+// saved.json in the data directory, or in the current one without a home
+pub fn default_path() -> PathBuf {
+    match history::data_dir() {
+        Some(dir) => dir.join("saved.json"),
+        None => PathBuf::from("boggle-saved.json"),
+    }
+}
+
+impl SavedGame {
+    pub fn new(game: &Game, minutes: f64, seconds_played: u64, seconds_left: u64) -> SavedGame {
+        SavedGame {
+            game: GameRecord::new(game, minutes, seconds_played, history::now()),
+            seconds_left,
+        }
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<SavedGame> {
+        let file = File::open(path)?;
+        serde_json::from_reader(BufReader::new(file))
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    // see `history::write_atomically`
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        history::write_atomically(path, |file| {
+            serde_json::to_writer_pretty(file, self).map_err(io::Error::other)
+        })
+    }
+
+    // the round as it was when saved. the guesses are made again in the
+    // order they were first made, so they land in the same lists
+    pub fn restore(&self, dictionary: Option<Arc<Dictionary>>) -> Result<Game, BoardError> {
+        let record = &self.game;
+        let board = Board::from_code_and_seed(&record.board, record.seed)?;
+        let mut game = Game::new(board, Player::new(record.player.clone()), dictionary);
+        game.rules = record.rules.clone();
+        for guess in &record.guesses {
+            let _ = game.add_guess_at(guess.word.clone(), guess.at_secs.unwrap_or(0));
+        }
+        game.guesses.add_penalty(record.penalty);
+        Ok(game)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::fs;
+    use std::process;
+
+    fn game() -> Game {
+        let board = Board::from_seed(4, 4, 99);
+        let words: Vec<String> = board.letters().iter().map(|c| c.to_string()).collect();
+        let dictionary = Arc::new(Dictionary::from_words(words));
+        let mut game = Game::new(board, Player::new(String::from("eric")), Some(dictionary));
+        game.rules.min_word_length = 1;
+        let first = game.board.letters()[0].to_string();
+        let _ = game.add_guess_at(first.clone(), 2);
+        let _ = game.add_guess_at(String::from("zzz"), 4);
+        let _ = game.add_guess_at(first, 6);
+        game.guesses.add_penalty(1);
+        game
+    }
+
+    #[test]
+    fn restoring_gives_back_the_same_round() {
+        let game = game();
+        let saved = SavedGame::new(&game, 3.0, 10, 170);
+        let restored = saved.restore(game.dictionary.clone()).unwrap();
+        assert_eq!(restored.board.code(), game.board.code());
+        assert_eq!(restored.board.seed(), Some(99));
+        assert_eq!(restored.player.name, "eric");
+        assert_eq!(restored.rules, game.rules);
+        assert_eq!(restored.guesses.valid().len(), 1);
+        assert_eq!(restored.guesses.invalid().len(), 1);
+        assert_eq!(restored.guesses.duplicates().len(), 1);
+        assert_eq!(restored.guesses.penalty(), 1);
+        assert_eq!(restored.guesses.score(), game.guesses.score());
+        assert_eq!(restored.guesses.valid()[0].at_secs(), Some(2));
+    }
+
+    #[test]
+    fn saved_games_survive_the_file() {
+        let path = env::temp_dir()
+            .join(format!("boggle-save-{}", process::id()))
+            .join("saved.json");
+        let saved = SavedGame::new(&game(), 2.5, 10, 140);
+        saved.save(&path).unwrap();
+        assert_eq!(SavedGame::load(&path).unwrap(), saved);
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+
+        let error = SavedGame::load(&path).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::NotFound);
+    }
+}
@@ -0,0 +1,1224 @@
+//! A sitting at the game: the welcome banner, as many rounds as the player
+//! wants and the menu in between, reading from any `BufRead` and writing to any `Write`.
+//! The binary runs it on stdin/stdout, tests run it on scripted input
+
+use std::collections::VecDeque;
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use rand::{thread_rng, Rng};
+
+use board::{Board, BOARD_DIMENSIONS};
+use clock::Clock;
+use command::{self, Command};
+use console::Console;
+use dictionary::Dictionary;
+use error::{Error, Result};
+use frequency::Frequencies;
+use game::{Game, Player};
+use hint::{self, Hint, HINT_KINDS};
+use export::{self, Export, Report};
+use history::{self, GameRecord, History};
+use journal::{Entry, Journal};
+use multiplayer::Cancellation;
+use opponent::{Computer, Difficulty, Opponent};
+use profile::{Preferences, Profile, ProfileStore, Theme};
+use rejection::Rejection;
+use rules::Rules;
+use save::{self, SavedGame};
+use timer::Timer;
+
+const NO_DICTIONARY_WARNING: &str = "\nWARNING: There was no dictionary file available! \
+    \nThe game will not check that your words exist in the dictionary.\n \
+    \nFor future games, please put a text file named \"dictionary.txt\" \
+    in the same directory as this program. \
+    \nExample dictionary: \
+    \nhttps://www.wordgamedictionary.com/twl06/download/twl06.txt\n\n\n";
+
+// largest board the settings prompt accepts in either direction
+const MAX_BOARD_SIZE: usize = 10;
+
+// more than this and the last player waits all evening
+const MAX_HOT_SEAT_PLAYERS: usize = 8;
+
+// how long a round lasts and how big its board is
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Settings {
+    pub minutes: f64,
+    pub rows: usize,
+    pub cols: usize,
+}
+
+impl Default for Settings {
+    fn default() -> Settings {
+        Settings {
+            minutes: 3.0,
+            rows: BOARD_DIMENSIONS,
+            cols: BOARD_DIMENSIONS,
+        }
+    }
+}
+
+// who else is playing a round, which decides whether it can be saved
+enum Company<'a> {
+    Nobody,
+    HotSeat,
+    Computer(&'a mut Opponent),
+}
+
+// how a round came to an end
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Ending {
+    // time ran out, or the player quit or closed the input
+    Over,
+    // put aside with `:save`
+    Saved,
+    // cut short by Ctrl-C and saved, so it can be picked up again
+    Interrupted,
+}
+
+// what the player can do between rounds
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum MenuChoice {
+    NewGame,
+    SameSettings,
+    ReplayBoard,
+    HotSeat,
+    Computer,
+    Stats,
+    Settings,
+    Quit,
+}
+
+pub struct GameSession<R, W: Write, C> {
+    input: R,
+    output: Console<W>,
+    clock: C,
+    dictionary: Option<Arc<Dictionary>>,
+    // how common words are, for the computer player
+    frequencies: Option<Arc<Frequencies>>,
+    // boards for the next rounds, random ones are used once they run out
+    boards: VecDeque<Board>,
+    player: Player,
+    settings: Settings,
+    rules: Rules,
+    // every finished round, oldest first
+    games: Vec<Game>,
+    // where finished rounds are saved, if anywhere
+    history: Option<History>,
+    // the file given with --output, if any
+    export: Option<Export>,
+    // where `:save` and interrupted rounds go
+    save_path: PathBuf,
+    // set while an interrupt has cut a read short, see `ChannelInput::interrupted`
+    interrupted: Option<Arc<AtomicBool>>,
+    // where rounds are journaled as they're played, if anywhere,
+    // and the journal of the round being played
+    journal_path: Option<PathBuf>,
+    journal: Option<Journal>,
+    // saved players to pick from, and the one playing
+    profiles: Option<ProfileStore>,
+    profile: Option<Profile>,
+}
+
+impl<R: BufRead, W: Write, C: Clock + Clone> GameSession<R, W, C> {
+    pub fn new(
+        input: R,
+        output: Console<W>,
+        clock: C,
+        dictionary: Option<Dictionary>,
+    ) -> GameSession<R, W, C> {
+        GameSession {
+            input,
+            output,
+            clock,
+            dictionary: dictionary.map(Arc::new),
+            frequencies: None,
+            boards: VecDeque::new(),
+            player: Player::new(String::new()),
+            settings: Settings::default(),
+            rules: Rules::default(),
+            games: vec![],
+            history: None,
+            export: None,
+            save_path: save::default_path(),
+            interrupted: None,
+            journal_path: None,
+            journal: None,
+            profiles: None,
+            profile: None,
+        }
+    }
+
+    // play the next round on `board` instead of a random one.
+    // boards queue up, one per round, in the order given
+    pub fn use_board(&mut self, board: Board) {
+        self.boards.push_back(board);
+    }
+
+    pub fn output(&self) -> &W {
+        self.output.get_ref()
+    }
+
+    pub fn settings(&self) -> Settings {
+        self.settings
+    }
+
+    // the computer player knows the words commonest in `frequencies` first
+    pub fn set_frequencies(&mut self, frequencies: Frequencies) {
+        self.frequencies = Some(Arc::new(frequencies));
+    }
+
+    // rules for every round from now on
+    pub fn set_rules(&mut self, rules: Rules) {
+        self.rules = rules;
+    }
+
+    pub fn games(&self) -> &[Game] {
+        &self.games
+    }
+
+    // save every finished round to `history`
+    pub fn set_history(&mut self, history: History) {
+        self.history = Some(history);
+    }
+
+    // also write every round of this sitting to `export`
+    pub fn set_export(&mut self, export: Export) {
+        self.export = Some(export);
+    }
+
+    // save rounds to `path` instead of the default
+    pub fn set_save_path<P: Into<PathBuf>>(&mut self, path: P) {
+        self.save_path = path.into();
+    }
+
+    // lets an interrupted round be saved rather than lost
+    pub fn set_interrupted(&mut self, interrupted: Arc<AtomicBool>) {
+        self.interrupted = Some(interrupted);
+    }
+
+    // write every round to a journal at `path` as it's played,
+    // so `boggle recover` can score it after a crash
+    pub fn set_journal<P: Into<PathBuf>>(&mut self, path: P) {
+        self.journal_path = Some(path.into());
+    }
+
+    // let the player pick a profile from `profiles` at the start,
+    // and keep their preferences and stats there
+    pub fn set_profiles(&mut self, profiles: ProfileStore) {
+        self.profiles = Some(profiles);
+    }
+
+    // play as `profile` without asking who's playing
+    pub fn use_profile(&mut self, profile: Profile) {
+        self.profile = Some(profile);
+    }
+
+    pub fn profile(&self) -> Option<&Profile> {
+        self.profile.as_ref()
+    }
+
+    // setup, the first round, then the menu until the player quits.
+    // closing the input during a round ends it early, at the menu it quits,
+    // and during setup it's an `Error::Eof`
+    pub fn run(&mut self) -> Result<()> {
+        writeln!(self.output, "Welcome to Boggle®")?;
+        writeln!(self.output, "==================\n")?;
+
+        if self.profiles.is_some() && self.profile.is_none() {
+            self.pick_profile()?;
+        }
+        if let Some(profile) = self.profile.clone() {
+            self.apply_preferences(&profile.preferences)?;
+            self.save_profile()?;
+        }
+
+        // a dictionary is not required to play the game,
+        // but user should understand the consequences
+        if self.dictionary.is_none() {
+            self.output.warn(NO_DICTIONARY_WARNING)?;
+        }
+
+        // a profile that has played before already knows how long to play for
+        let known_minutes = self.profile
+            .as_ref()
+            .and_then(|p| p.preferences.minutes);
+        match known_minutes {
+            Some(minutes) => self.settings.minutes = minutes,
+            None => {
+                // to "prompt" on the same line, call write! followed by a flush
+                self.prompt("For how many minutes would you like to play? (decimals OK) ")?;
+                self.settings.minutes = self.read_play_time()?;
+                self.output.clean_prev_line()?;
+                self.remember_settings()?;
+            }
+        }
+
+        let name = match self.profile {
+            Some(ref profile) => profile.name.clone(),
+            None => {
+                self.prompt("Please enter your name: ")?;
+                let name = self.read_line()?;
+                self.output.clean_prev_line()?;
+                name.trim().to_string()
+            }
+        };
+        self.player = Player::new(name);
+
+        let board = self.next_board();
+        self.play(board)?;
+
+        match self.menu_loop() {
+            Err(Error::Eof) => (),
+            other => other?,
+        }
+        writeln!(self.output, "\nThanks for playing!")?;
+        Ok(())
+    }
+
+    // carry on with a round saved by `:save` or an interrupt, with the time
+    // that was left, then go to the menu as usual. the save is removed once
+    // the round is over
+    pub fn resume(&mut self, saved: SavedGame) -> Result<()> {
+        writeln!(self.output, "Welcome back to Boggle®")?;
+        writeln!(self.output, "=======================\n")?;
+
+        // the player's profile, if they have one, for its theme and dictionary
+        if self.profile.is_none() {
+            if let Some(ref store) = self.profiles {
+                self.profile = store.find(&saved.game.player).unwrap_or(None);
+            }
+        }
+        if let Some(profile) = self.profile.clone() {
+            self.apply_preferences(&profile.preferences)?;
+        }
+
+        let game = saved
+            .restore(self.dictionary.clone())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        self.player = game.player.clone();
+        self.rules = game.rules.clone();
+        self.settings.minutes = saved.game.minutes;
+        self.settings.rows = game.board.rows();
+        self.settings.cols = game.board.cols();
+
+        let mut timer = Timer::with_clock(self.clock.clone());
+        timer.set_play_time(self.settings.minutes);
+        timer.carry_on(saved.game.seconds_played, saved.seconds_left);
+        writeln!(
+            self.output,
+            "Picking up where you left off, with {} found so far",
+            game.guesses.valid().len()
+        )?;
+        if self.play_game(game, timer)? == Ending::Over {
+            let _ = fs::remove_file(&self.save_path);
+        }
+
+        match self.menu_loop() {
+            Err(Error::Eof) => (),
+            other => other?,
+        }
+        writeln!(self.output, "\nThanks for playing!")?;
+        Ok(())
+    }
+
+    // choose a saved profile by number, or make a new one by typing a name.
+    // profiles that can't be read are skipped with a warning
+    fn pick_profile(&mut self) -> Result<()> {
+        let loaded = match self.profiles {
+            Some(ref store) => store.load().map_err(|e| (store.path().display().to_string(), e)),
+            None => return Ok(()),
+        };
+        let profiles = match loaded {
+            Ok(profiles) => profiles,
+            Err((path, e)) => {
+                self.output
+                    .warn(&format!("Couldn't read profiles from {}: {}\n", path, e))?;
+                self.profiles = None;
+                return Ok(());
+            }
+        };
+
+        if profiles.is_empty() {
+            self.prompt("Please enter your name: ")?;
+        } else {
+            writeln!(self.output, "Who's playing?")?;
+            for (i, profile) in profiles.iter().enumerate() {
+                writeln!(
+                    self.output,
+                    "  [{}] {} ({} games, best {})",
+                    i + 1,
+                    profile.name,
+                    profile.stats.games,
+                    profile.stats.best_score
+                )?;
+            }
+            self.prompt("Pick a number, or type a new name: ")?;
+        }
+        let profile = loop {
+            let line = self.read_line()?;
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let picked = line.parse::<usize>()
+                .ok()
+                .and_then(|n| n.checked_sub(1))
+                .and_then(|i| profiles.get(i));
+            let named = profiles
+                .iter()
+                .find(|p| p.name.to_lowercase() == line.to_lowercase());
+            break match picked.or(named) {
+                Some(profile) => profile.clone(),
+                None => Profile::new(line),
+            };
+        };
+        self.output.clean_prev_line()?;
+        self.profile = Some(profile);
+        Ok(())
+    }
+
+    fn apply_preferences(&mut self, preferences: &Preferences) -> Result<()> {
+        if let Some(minutes) = preferences.minutes {
+            self.settings.minutes = minutes;
+        }
+        if let Some((rows, cols)) = preferences.board_size {
+            self.settings.rows = rows;
+            self.settings.cols = cols;
+        }
+        self.output.set_colors(preferences.theme == Theme::Color);
+        if let Some(ref path) = preferences.dictionary {
+            match Dictionary::open(path) {
+                Ok(dictionary) => self.dictionary = Some(Arc::new(dictionary)),
+                Err(e) => self.output.warn(&format!(
+                    "Couldn't open the dictionary {}: {}\n",
+                    path.display(),
+                    e
+                ))?,
+            }
+        }
+        Ok(())
+    }
+
+    // the current settings become the profile's defaults
+    fn remember_settings(&mut self) -> Result<()> {
+        let settings = self.settings;
+        match self.profile {
+            Some(ref mut profile) => {
+                profile.preferences.minutes = Some(settings.minutes);
+                profile.preferences.board_size = Some((settings.rows, settings.cols));
+            }
+            None => return Ok(()),
+        }
+        self.save_profile()
+    }
+
+    // like the history, failing to save a profile only gets a warning
+    fn save_profile(&mut self) -> Result<()> {
+        let saved = match (self.profiles.as_ref(), self.profile.as_ref()) {
+            (Some(store), Some(profile)) => store
+                .save(profile)
+                .map_err(|e| (store.path().display().to_string(), e)),
+            _ => return Ok(()),
+        };
+        if let Err((path, e)) = saved {
+            self.output
+                .warn(&format!("Couldn't save your profile to {}: {}\n", path, e))?;
+        }
+        Ok(())
+    }
+
+    fn menu_loop(&mut self) -> Result<()> {
+        loop {
+            match self.menu()? {
+                MenuChoice::NewGame => {
+                    self.ask_settings()?;
+                    let board = self.next_board();
+                    self.play(board)?;
+                }
+                MenuChoice::SameSettings => {
+                    let board = self.next_board();
+                    self.play(board)?;
+                }
+                MenuChoice::ReplayBoard => {
+                    let board = match self.games.last() {
+                        Some(game) => game.board.clone(),
+                        None => self.next_board(),
+                    };
+                    self.play(board)?;
+                }
+                MenuChoice::HotSeat => self.hot_seat()?,
+                MenuChoice::Computer => self.against_computer()?,
+                MenuChoice::Stats => self.show_stats()?,
+                MenuChoice::Settings => self.ask_settings()?,
+                MenuChoice::Quit => return Ok(()),
+            }
+        }
+    }
+
+    fn menu(&mut self) -> Result<MenuChoice> {
+        writeln!(self.output, "\nWhat next?")?;
+        writeln!(self.output, "  [n] New game")?;
+        writeln!(self.output, "  [a] Again, with the same settings")?;
+        writeln!(self.output, "  [r] Replay the same board")?;
+        writeln!(self.output, "  [h] Hot-seat game for several players")?;
+        writeln!(self.output, "  [c] Play against the computer")?;
+        writeln!(self.output, "  [s] Stats")?;
+        writeln!(self.output, "  [o] Settings")?;
+        writeln!(self.output, "  [q] Quit")?;
+        loop {
+            self.prompt("> ")?;
+            let line = self.read_line()?;
+            let choice = match line.trim().to_lowercase().as_str() {
+                "n" => MenuChoice::NewGame,
+                "a" => MenuChoice::SameSettings,
+                "r" => MenuChoice::ReplayBoard,
+                "h" => MenuChoice::HotSeat,
+                "c" => MenuChoice::Computer,
+                "s" => MenuChoice::Stats,
+                "o" => MenuChoice::Settings,
+                "q" => MenuChoice::Quit,
+                _ => {
+                    writeln!(self.output, "Pick one of n, a, r, h, c, s, o or q")?;
+                    continue;
+                }
+            };
+            return Ok(choice);
+        }
+    }
+
+    fn ask_settings(&mut self) -> Result<()> {
+        self.prompt("For how many minutes would you like to play? (decimals OK) ")?;
+        self.settings.minutes = self.read_play_time()?;
+        self.output.clean_prev_line()?;
+
+        let current = format!("{}x{}", self.settings.rows, self.settings.cols);
+        self.prompt(&format!(
+            "How big should the board be? (e.g. 5x5, enter keeps {}) ",
+            current
+        ))?;
+        loop {
+            let line = self.read_line()?;
+            let line = line.trim();
+            if line.is_empty() {
+                break;
+            }
+            match parse_board_size(line) {
+                Some((rows, cols)) => {
+                    self.settings.rows = rows;
+                    self.settings.cols = cols;
+                    break;
+                }
+                None => writeln!(
+                    self.output,
+                    "Enter a size like 4x4, up to {0}x{0}!",
+                    MAX_BOARD_SIZE
+                )?,
+            }
+        }
+        self.output.clean_prev_line()?;
+
+        if self.profile.is_some() {
+            self.ask_preferences()?;
+        }
+        self.remember_settings()
+    }
+
+    // the things only a profile remembers: which dictionary and theme
+    fn ask_preferences(&mut self) -> Result<()> {
+        let mut preferences = match self.profile {
+            Some(ref profile) => profile.preferences.clone(),
+            None => return Ok(()),
+        };
+        let current = match preferences.dictionary {
+            Some(ref path) => path.display().to_string(),
+            None => String::from("dictionary.txt"),
+        };
+        self.prompt(&format!("Dictionary file? (enter keeps {}) ", current))?;
+        loop {
+            let line = self.read_line()?;
+            let line = line.trim();
+            if line.is_empty() {
+                break;
+            }
+            match Dictionary::open(line) {
+                Ok(dictionary) => {
+                    self.dictionary = Some(Arc::new(dictionary));
+                    preferences.dictionary = Some(line.into());
+                    break;
+                }
+                Err(e) => writeln!(self.output, "Couldn't open {}: {}", line, e)?,
+            }
+        }
+        self.output.clean_prev_line()?;
+
+        self.prompt(&format!(
+            "Theme, color or mono? (enter keeps {}) ",
+            preferences.theme
+        ))?;
+        loop {
+            let line = self.read_line()?;
+            let line = line.trim().to_lowercase();
+            if line.is_empty() {
+                break;
+            }
+            match Theme::from_name(&line) {
+                Some(theme) => {
+                    preferences.theme = theme;
+                    break;
+                }
+                None => writeln!(self.output, "Enter color or mono!")?,
+            }
+        }
+        self.output.clean_prev_line()?;
+        self.output.set_colors(preferences.theme == Theme::Color);
+
+        if let Some(ref mut profile) = self.profile {
+            profile.preferences = preferences;
+        }
+        Ok(())
+    }
+
+    fn show_stats(&mut self) -> Result<()> {
+        writeln!(self.output, "\nStats for this session\n======================")?;
+        let played = self.games.len();
+        let scores: Vec<usize> = self.games.iter().map(|g| g.guesses.score()).collect();
+        let best = scores.iter().cloned().max().unwrap_or(0);
+        let average = if played > 0 {
+            scores.iter().sum::<usize>() as f64 / played as f64
+        } else {
+            0.0
+        };
+        // first longest word wins a tie
+        let mut longest = "";
+        for game in self.games.iter() {
+            for guess in game.guesses.valid() {
+                if guess.word().len() > longest.len() {
+                    longest = guess.word();
+                }
+            }
+        }
+        writeln!(self.output, "Games played: {}", played)?;
+        writeln!(self.output, "Best score: {}", best)?;
+        writeln!(self.output, "Average score: {:.1}", average)?;
+        writeln!(self.output, "Longest word: {}", longest)?;
+
+        if let Some(ref profile) = self.profile {
+            let stats = &profile.stats;
+            writeln!(
+                self.output,
+                "\nAll games for {}\n================",
+                profile.name
+            )?;
+            writeln!(self.output, "Games played: {}", stats.games)?;
+            writeln!(self.output, "Best score: {}", stats.best_score)?;
+            writeln!(self.output, "Average score: {:.1}", stats.average())?;
+            writeln!(self.output, "Longest word: {}", stats.longest_word)?;
+        }
+        Ok(())
+    }
+
+    // the next board queued up with `use_board`, or a random one for the current settings
+    fn next_board(&mut self) -> Board {
+        match self.boards.pop_front() {
+            Some(board) => board,
+            None => Board::random(self.settings.rows, self.settings.cols),
+        }
+    }
+
+    // players take turns on the same board, each against the full clock,
+    // with the board cleared away before the next one sits down
+    fn hot_seat(&mut self) -> Result<()> {
+        self.prompt(&format!(
+            "How many players? (2 to {}) ",
+            MAX_HOT_SEAT_PLAYERS
+        ))?;
+        let count = loop {
+            let line = self.read_line()?;
+            match line.trim().parse::<usize>() {
+                Ok(n) if (2..=MAX_HOT_SEAT_PLAYERS).contains(&n) => break n,
+                _ => writeln!(
+                    self.output,
+                    "Enter a number from 2 to {}!",
+                    MAX_HOT_SEAT_PLAYERS
+                )?,
+            }
+        };
+        let mut players = vec![];
+        for i in 1..(count + 1) {
+            self.prompt(&format!("Name of player {}: ", i))?;
+            let name = self.read_line()?;
+            let name = match name.trim() {
+                "" => format!("Player {}", i),
+                name => name.to_string(),
+            };
+            players.push(Player::new(name));
+        }
+
+        let board = self.next_board();
+        let mut games = vec![];
+        // closing the input stops the game, but everyone who played still gets results
+        let mut closed = false;
+        for player in players {
+            self.output.clear_screen()?;
+            self.prompt(&format!(
+                "\nPass to {}, press enter when you're ready ",
+                player.name
+            ))?;
+            if !self.wait_for_enter()? {
+                closed = true;
+                break;
+            }
+
+            let mut timer = Timer::with_clock(self.clock.clone());
+            timer.set_play_time(self.settings.minutes);
+            let mut game = Game::new(board.clone(), player, self.dictionary.clone());
+            game.rules = self.rules.clone();
+            self.play_round(&mut game, &mut timer, Company::HotSeat)?;
+            writeln!(
+                self.output,
+                "That's it, {}! You scored {}",
+                game.player.name,
+                game.guesses.score()
+            )?;
+            self.save(&mut game, timer.elapsed_secs())?;
+            games.push(game);
+
+            self.prompt("Press enter to hide the board ")?;
+            if !self.wait_for_enter()? {
+                closed = true;
+                break;
+            }
+        }
+        self.output.clear_screen()?;
+
+        // shared words are crossed off, like in the board game
+        writeln!(self.output, "\n{}", Cancellation::new(&games))?;
+        self.games.extend(games);
+        if closed {
+            return Err(Error::Eof);
+        }
+        Ok(())
+    }
+
+    // false if the input closed instead
+    fn wait_for_enter(&mut self) -> Result<bool> {
+        match self.read_line() {
+            Ok(_) => Ok(true),
+            Err(Error::Eof) => {
+                writeln!(self.output)?;
+                Ok(false)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    // one round on `board`, followed by its results
+    fn play(&mut self, board: Board) -> Result<()> {
+        let mut timer = Timer::with_clock(self.clock.clone());
+        timer.set_play_time(self.settings.minutes);
+        let mut game = Game::new(board, self.player.clone(), self.dictionary.clone());
+        game.rules = self.rules.clone();
+        self.play_game(game, timer)?;
+        Ok(())
+    }
+
+    // a round set up by `play` or `resume`. a round that's been saved
+    // isn't over, so it doesn't go in the history yet
+    fn play_game(&mut self, mut game: Game, mut timer: Timer<C>) -> Result<Ending> {
+        let ending = self.play_round(&mut game, &mut timer, Company::Nobody)?;
+        if ending == Ending::Saved {
+            return Ok(ending);
+        }
+
+        writeln!(self.output, "Nice job! Here are your results:")?;
+        writeln!(self.output, "{}", game.guesses)?;
+        if ending == Ending::Over {
+            self.save(&mut game, timer.elapsed_secs())?;
+        }
+        self.games.push(game);
+        Ok(ending)
+    }
+
+    // a round against a computer player, scored like a hot-seat game.
+    // the computer only knows words from the dictionary, so it needs one
+    fn against_computer(&mut self) -> Result<()> {
+        let dictionary = match self.dictionary {
+            Some(ref dictionary) => dictionary.clone(),
+            None => {
+                writeln!(self.output, "The computer needs a dictionary to play against you")?;
+                return Ok(());
+            }
+        };
+        self.prompt("Difficulty, easy, medium or hard? (enter for medium) ")?;
+        let difficulty = loop {
+            let line = self.read_line()?;
+            match line.trim().to_lowercase().as_str() {
+                "" => break Difficulty::default(),
+                name => match Difficulty::from_name(name) {
+                    Some(difficulty) => break difficulty,
+                    None => writeln!(self.output, "Type easy, medium or hard!")?,
+                },
+            }
+        };
+
+        let board = self.next_board();
+        let mut timer = Timer::with_clock(self.clock.clone());
+        timer.set_play_time(self.settings.minutes);
+        let seconds = (self.settings.minutes * 60.0) as u64;
+        let mut computer = Computer::new(difficulty, dictionary.clone(), thread_rng().gen());
+        if let Some(ref frequencies) = self.frequencies {
+            computer.set_frequencies(frequencies.clone());
+        }
+        let mut opponent = Opponent::new(board.clone(), dictionary, &mut computer, seconds);
+        let mut game = Game::new(board, self.player.clone(), self.dictionary.clone());
+        game.rules = self.rules.clone();
+        opponent.game.rules = self.rules.clone();
+
+        self.play_round(&mut game, &mut timer, Company::Computer(&mut opponent))?;
+        // the computer stops when the player does
+        opponent.catch_up(timer.elapsed_secs());
+
+        writeln!(self.output, "Nice job! Here are your results:")?;
+        writeln!(self.output, "{}", game.guesses)?;
+        self.save(&mut game, timer.elapsed_secs())?;
+        let mut games = vec![game, opponent.game];
+        writeln!(self.output, "\n{}", Cancellation::new(&games))?;
+        // only the player's round is theirs to keep
+        games.truncate(1);
+        self.games.extend(games);
+        Ok(())
+    }
+
+    // not being able to save is worth a warning, not the end of the game
+    fn save(&mut self, game: &mut Game, seconds_played: u64) -> Result<()> {
+        // in a hot-seat game only the profile's own turn counts for it
+        if let Some(ref mut profile) = self.profile {
+            if profile.name == game.player.name {
+                profile.stats.record(game);
+            }
+        }
+        self.save_profile()?;
+
+        // the same record goes in the history and the export
+        let record = GameRecord::new(game, self.settings.minutes, seconds_played, history::now());
+        let exported = match self.export {
+            Some(ref mut export) => {
+                let report = Report::from_record(record.clone(), export::missed(game));
+                export
+                    .add(report)
+                    .map_err(|e| (export.path().display().to_string(), e))
+            }
+            None => Ok(()),
+        };
+        if let Err((path, e)) = exported {
+            self.output
+                .warn(&format!("Couldn't write the results to {}: {}\n", path, e))?;
+        }
+
+        let history = match self.history {
+            Some(ref history) => history,
+            None => return Ok(()),
+        };
+        if let Err(e) = history.append(&record) {
+            let warning = format!(
+                "Couldn't save this game to {}: {}\n",
+                history.path().display(),
+                e
+            );
+            self.output.warn(&warning)?;
+        }
+        Ok(())
+    }
+
+    // a round, journaled unless it's a hot-seat turn. the journal is only
+    // removed when the round ends, an error or a crash leaves it behind
+    fn play_round(
+        &mut self,
+        game: &mut Game,
+        timer: &mut Timer<C>,
+        company: Company,
+    ) -> Result<Ending> {
+        let path = match company {
+            Company::HotSeat => None,
+            _ => self.journal_path.clone(),
+        };
+        if let Some(path) = path {
+            match Journal::start(&path, game, self.settings.minutes) {
+                Ok(journal) => self.journal = Some(journal),
+                Err(e) => self.output.warn(&format!(
+                    "Couldn't keep a journal at {}: {}\n",
+                    path.display(),
+                    e
+                ))?,
+            }
+        }
+
+        let ending = self.play_turns(game, timer, company)?;
+        if let Some(journal) = self.journal.take() {
+            let _ = journal.finish();
+        }
+        Ok(ending)
+    }
+
+    // note down what happened in the round. if the journal can't be written
+    // the round goes on without one
+    fn journal(&mut self, entry: Entry) -> Result<()> {
+        let failed = match self.journal {
+            Some(ref mut journal) => match journal.write(&entry) {
+                Ok(()) => None,
+                Err(e) => Some(format!("{}: {}", journal.path().display(), e)),
+            },
+            None => None,
+        };
+        if let Some(failed) = failed {
+            self.journal = None;
+            self.output
+                .warn(&format!("Stopped journaling this round, couldn't write {}\n", failed))?;
+        }
+        Ok(())
+    }
+
+    fn play_turns(
+        &mut self,
+        game: &mut Game,
+        timer: &mut Timer<C>,
+        mut company: Company,
+    ) -> Result<Ending> {
+        writeln!(self.output, "Hello {}, here is your game:", game.player.name)?;
+        writeln!(
+            self.output,
+            "Enter as many words as possible in {} mins!",
+            timer.max_time_minutes
+        )?;
+        writeln!(self.output, "{}", game.board)?;
+        writeln!(self.output, "(type :help for commands)")?;
+
+        // what the player sees, which `:shuffle` turns around
+        let mut view = game.board.clone();
+        // suggestion the player can still take in a practice round
+        let mut offer: Option<String> = None;
+        timer.start();
+        loop {
+            if timer.is_time_up() {
+                break;
+            }
+            match company {
+                Company::Computer(ref mut rival) => {
+                    rival.catch_up(timer.elapsed_secs());
+                    let found = rival.found();
+                    writeln!(
+                        self.output,
+                        "Now start typing words! ({} seconds left, the computer has found {} word{})",
+                        timer.get_remaining_time(),
+                        found,
+                        if found == 1 { "" } else { "s" }
+                    )?
+                }
+                _ => writeln!(
+                    self.output,
+                    "Now start typing words! ({} seconds left)",
+                    timer.get_remaining_time()
+                )?,
+            }
+
+            // get guess, no more input means the player is done
+            let line = match self.read_line() {
+                Ok(line) => line,
+                Err(Error::Eof) => return self.end_early(game, timer, &company),
+                Err(e) => return Err(e),
+            };
+
+            // add to guesses if time is remaining
+            // (i.e. don't allow guesses after time has run out)
+            self.output.clean_prev_line()?;
+            self.output.clean_prev_line()?;
+
+            let line = line.trim();
+            let command = Command::parse(line);
+            // an offer only stands until the next line
+            let offered = offer.take();
+            if timer.is_time_up() {
+                if command.is_none() {
+                    writeln!(
+                        self.output,
+                        "Oooh, so close! But \"{}\" was entered after time ran out",
+                        line
+                    )?;
+                }
+                break;
+            }
+            match command {
+                // pressing enter on its own isn't a guess,
+                // but in practice it takes the last suggestion
+                None if line.is_empty() => {
+                    if let Some(word) = offered {
+                        let at_secs = timer.elapsed_secs();
+                        game.guesses.undo();
+                        let _ = game.add_guess_at(word.clone(), at_secs);
+                        writeln!(self.output, "Took \"{}\" instead", word)?;
+                        self.journal(Entry::Undo)?;
+                        self.journal(Entry::Guess { word, at_secs })?;
+                    }
+                }
+                None => offer = self.guess(game, line, timer.elapsed_secs())?,
+                Some(Command::Quit) => break,
+                Some(Command::Save) => match company {
+                    Company::Nobody => {
+                        if self.save_round(game, timer)? {
+                            return Ok(Ending::Saved);
+                        }
+                    }
+                    _ => writeln!(self.output, "Only rounds you play alone can be saved")?,
+                },
+                Some(command) => match self.run_command(command, game, timer, &mut view) {
+                    Ok(()) => (),
+                    // input closed, or Ctrl-C, while paused
+                    Err(Error::Eof) => return self.end_early(game, timer, &company),
+                    Err(e) => return Err(e),
+                },
+            }
+        }
+        Ok(Ending::Over)
+    }
+
+    // the input closed during the round. Ctrl-C still shows the results,
+    // but a round played alone is kept for later
+    fn end_early(&mut self, game: &Game, timer: &Timer<C>, company: &Company) -> Result<Ending> {
+        writeln!(self.output)?;
+        let interrupted = self.interrupted
+            .as_ref()
+            .is_some_and(|flag| flag.load(Ordering::SeqCst));
+        if interrupted && !timer.is_time_up() {
+            if let Company::Nobody = *company {
+                if self.save_round(game, timer)? {
+                    return Ok(Ending::Interrupted);
+                }
+            }
+        }
+        Ok(Ending::Over)
+    }
+
+    // write the round to the save file, false (with a warning) if that failed
+    fn save_round(&mut self, game: &Game, timer: &Timer<C>) -> Result<bool> {
+        let left = timer.get_remaining_time().max(0) as u64;
+        let saved = SavedGame::new(game, self.settings.minutes, timer.elapsed_secs(), left);
+        let path = self.save_path.clone();
+        match saved.save(&path) {
+            Ok(()) => {
+                writeln!(
+                    self.output,
+                    "Saved with {} seconds left, `boggle resume` picks it up again",
+                    left
+                )?;
+                Ok(true)
+            }
+            Err(e) => {
+                self.output
+                    .warn(&format!("Couldn't save to {}: {}\n", path.display(), e))?;
+                Ok(false)
+            }
+        }
+    }
+
+    // add a guess, telling the player why it didn't count and what they may
+    // have meant. returns the suggestion if the player can take it with enter
+    fn guess(&mut self, game: &mut Game, word: &str, secs: u64) -> Result<Option<String>> {
+        let result = game.add_guess_at(String::from(word), secs);
+        self.journal(Entry::Guess {
+            word: String::from(word),
+            at_secs: secs,
+        })?;
+        let rejection = match result {
+            Ok(_) => return Ok(None),
+            Err(rejection) => rejection,
+        };
+        write!(self.output, "\"{}\" doesn't count: {}", word, rejection)?;
+        let suggestion = match rejection {
+            // a repeat doesn't go on the list again
+            Rejection::Duplicate => None,
+            _ => game.guesses
+                .last()
+                .and_then(|g| g.suggestion())
+                .map(String::from),
+        };
+        match suggestion {
+            Some(ref suggestion) if game.rules.practice => writeln!(
+                self.output,
+                ". Did you mean \"{}\"? (press enter to take it)",
+                suggestion
+            )?,
+            Some(ref suggestion) => {
+                writeln!(self.output, ". Did you mean \"{}\"?", suggestion)?
+            }
+            None => writeln!(self.output)?,
+        }
+        if game.rules.practice {
+            Ok(suggestion)
+        } else {
+            Ok(None)
+        }
+    }
+
+    // commands never count as guesses
+    fn run_command(
+        &mut self,
+        command: Command,
+        game: &mut Game,
+        timer: &mut Timer<C>,
+        view: &mut Board,
+    ) -> Result<()> {
+        match command {
+            Command::Hint(kind) => {
+                let mut rng = thread_rng();
+                let kind = match kind {
+                    Some(kind) => kind,
+                    None => *rng.choose(&HINT_KINDS).unwrap_or(&HINT_KINDS[0]),
+                };
+                let found: Vec<String> = game.guesses
+                    .valid()
+                    .iter()
+                    .map(|g| g.word().to_uppercase())
+                    .collect();
+                // the tile is looked up on the board as the player sees it
+                let hint = match game.solutions() {
+                    Some(words) => hint::hint(kind, view, words, &found, &mut rng),
+                    None => {
+                        writeln!(self.output, "Hints need a dictionary")?;
+                        return Ok(());
+                    }
+                };
+                if let Hint::Tile(row, col) = hint {
+                    writeln!(self.output, "{}", view.highlight(row, col))?;
+                }
+                writeln!(self.output, "{}", hint)?;
+                if hint != Hint::NothingLeft {
+                    self.charge_for_hint(game, timer)?;
+                }
+            }
+            Command::Shuffle => {
+                *view = view.rotated();
+                writeln!(self.output, "{}", view)?;
+            }
+            Command::Pause => {
+                timer.pause();
+                self.prompt(&format!(
+                    "Paused with {} seconds left, press enter to carry on ",
+                    timer.get_remaining_time()
+                ))?;
+                let resumed = self.read_line();
+                timer.resume();
+                resumed?;
+                self.output.clean_prev_line()?;
+            }
+            Command::Time => {
+                writeln!(self.output, "{} seconds left", timer.get_remaining_time())?;
+            }
+            Command::Score => writeln!(self.output, "Score so far: {}", game.guesses.score())?,
+            Command::List => {
+                let words: Vec<&str> = game.guesses.valid().iter().map(|g| g.word()).collect();
+                if words.is_empty() {
+                    writeln!(self.output, "No words yet")?;
+                } else {
+                    writeln!(self.output, "Words so far: {}", words.join(", "))?;
+                }
+            }
+            Command::Undo => match game.guesses.undo() {
+                Some((guess, _)) => {
+                    writeln!(self.output, "Took back \"{}\"", guess)?;
+                    self.journal(Entry::Undo)?;
+                }
+                None => writeln!(self.output, "Nothing to undo")?,
+            },
+            Command::Help => {
+                for &(name, description) in command::HELP {
+                    writeln!(self.output, "  {:<9} {}", name, description)?;
+                }
+            }
+            // handled by the round itself
+            Command::Quit | Command::Save => (),
+            Command::Unknown(name) => writeln!(
+                self.output,
+                "Unknown command \"{}\", type :help for a list",
+                name
+            )?,
+        }
+        Ok(())
+    }
+
+    // hints cost whatever the rules say
+    fn charge_for_hint(&mut self, game: &mut Game, timer: &mut Timer<C>) -> Result<()> {
+        let points = game.rules.hint_points;
+        let seconds = game.rules.hint_seconds;
+        if points > 0 {
+            game.guesses.add_penalty(points);
+            writeln!(self.output, "That hint cost you {} points", points)?;
+            self.journal(Entry::Penalty { points })?;
+        }
+        if seconds > 0 {
+            timer.deduct(seconds);
+            writeln!(self.output, "That hint cost you {} seconds", seconds)?;
+        }
+        Ok(())
+    }
+
+    // get total game length in minutes
+    fn read_play_time(&mut self) -> Result<f64> {
+        loop {
+            let line = self.read_line()?;
+            match line.trim().parse::<f64>() {
+                Ok(n) => return Ok(n),
+                Err(_e) => writeln!(self.output, "Enter a number!")?,
+            }
+        }
+    }
+
+    fn prompt(&mut self, message: &str) -> io::Result<()> {
+        write!(self.output, "{}", message)?;
+        self.output.flush()
+    }
+
+    fn read_line(&mut self) -> Result<String> {
+        let mut line = String::new();
+        if self.input.read_line(&mut line)? == 0 {
+            return Err(Error::Eof);
+        }
+        Ok(line)
+    }
+}
+
+// "5x4" is 5 rows and 4 columns, a single number is a square board
+pub fn parse_board_size(size: &str) -> Option<(usize, usize)> {
+    let mut parts = size.split('x').map(|p| p.trim().parse::<usize>());
+    let (rows, cols) = match (parts.next(), parts.next(), parts.next()) {
+        (Some(Ok(n)), None, None) => (n, n),
+        (Some(Ok(rows)), Some(Ok(cols)), None) => (rows, cols),
+        _ => return None,
+    };
+    let valid = 1..(MAX_BOARD_SIZE + 1);
+    if valid.contains(&rows) && valid.contains(&cols) {
+        Some((rows, cols))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_board_size_forms() {
+        assert_eq!(parse_board_size("4x4"), Some((4, 4)));
+        assert_eq!(parse_board_size("5 x 3"), Some((5, 3)));
+        assert_eq!(parse_board_size("6"), Some((6, 6)));
+        assert_eq!(parse_board_size("0x4"), None);
+        assert_eq!(parse_board_size("11"), None);
+        assert_eq!(parse_board_size("4x4x4"), None);
+        assert_eq!(parse_board_size("big"), None);
+    }
+}
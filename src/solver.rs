@@ -0,0 +1,78 @@
+//! Find every dictionary word that can be played on a board
+
+use std::collections::BTreeSet;
+
+use board::Board;
+use dictionary::Dictionary;
+
+// all words from `dictionary` that can be traced on `board`,
+// uppercase and in alphabetical order
+pub fn solve(board: &Board, dictionary: &Dictionary) -> Vec<String> {
+    let mut found = BTreeSet::new();
+    let mut visited = vec![false; board.rows() * board.cols()];
+    let mut prefix = String::new();
+    for start in 0..visited.len() {
+        walk(board, dictionary, start, &mut visited, &mut prefix, &mut found);
+    }
+    found.into_iter().collect()
+}
+
+// depth-first search from `index`, abandoning any branch
+// once no dictionary word starts with the letters walked so far
+fn walk(
+    board: &Board,
+    dictionary: &Dictionary,
+    index: usize,
+    visited: &mut Vec<bool>,
+    prefix: &mut String,
+    found: &mut BTreeSet<String>,
+) {
+    let letter = match board.piece(index) {
+        Some(piece) => piece.letter(),
+        None => return,
+    };
+    prefix.push(letter);
+    if dictionary.has_prefix(prefix) {
+        if dictionary.contains(prefix) {
+            found.insert(prefix.clone());
+        }
+        visited[index] = true;
+        for &next in board.neighbors(index) {
+            if !visited[next] {
+                walk(board, dictionary, next, visited, prefix, found);
+            }
+        }
+        visited[index] = false;
+    }
+    prefix.pop();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn board(letters: &str) -> Board {
+        let faces: Vec<String> = letters.chars().map(|c| c.to_string()).collect();
+        Board::try_from_letters(4, 4, &faces).unwrap()
+    }
+
+    #[test]
+    fn finds_words_on_the_board() {
+        let board = board("CATSXXXXXXXXXXXX");
+        let dict = Dictionary::from_words(vec!["cat", "cats", "act", "tac", "dog", "scat"]);
+        assert_eq!(solve(&board, &dict), vec!["CAT", "CATS", "TAC"]);
+    }
+
+    #[test]
+    fn does_not_reuse_pieces() {
+        let board = board("ABXXXXXXXXXXXXXX");
+        let dict = Dictionary::from_words(vec!["ab", "aba", "ba"]);
+        assert_eq!(solve(&board, &dict), vec!["AB", "BA"]);
+    }
+
+    #[test]
+    fn empty_dictionary_finds_nothing() {
+        let board = board("CATSXXXXXXXXXXXX");
+        assert!(solve(&board, &Dictionary::default()).is_empty());
+    }
+}
@@ -0,0 +1,16 @@
+//! How a player, computer or otherwise, plays a round when nobody is typing.
+//! A strategy sees the board and how long the round lasts and answers with
+//! every guess it will make and when; the guesses are checked and scored like
+//! anyone else's. `Computer` is the built-in one, `arena` pits them against each other
+
+use board::Board;
+
+pub trait Strategy {
+    // how it's shown in results, e.g. "Computer (hard)"
+    fn name(&self) -> String;
+
+    // the guesses for a round of `seconds` on `board`, each with the
+    // second into the round it's made at. guesses at or after `seconds`
+    // come too late and don't count
+    fn play(&mut self, board: &Board, seconds: u64) -> Vec<(u64, String)>;
+}
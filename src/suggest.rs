@@ -0,0 +1,101 @@
+//! "Did you mean" for guesses that didn't count, picked from
+//! every word the solver found on the board
+
+// fewest single-letter insertions, deletions, changes or swaps of
+// two letters next to each other to turn `a` into `b`
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    // d[i][j] is the distance from the first i letters of `a` to the first j of `b`
+    let mut d = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in d[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..a.len() + 1 {
+        for j in 1..b.len() + 1 {
+            let change = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            let mut best = (d[i - 1][j] + 1)
+                .min(d[i][j - 1] + 1)
+                .min(d[i - 1][j - 1] + change);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                best = best.min(d[i - 2][j - 2] + 1);
+            }
+            d[i][j] = best;
+        }
+    }
+    d[a.len()][b.len()]
+}
+
+// how far off a guess can be and still get a suggestion.
+// short words are only allowed one slip, or everything looks like everything
+fn max_distance(len: usize) -> usize {
+    if len <= 4 {
+        1
+    } else {
+        2
+    }
+}
+
+// the closest of `solutions` to `guess` that isn't in `found`, if any is close enough.
+// `solutions` and `found` must be uppercase, a tie goes to the first solution
+pub fn closest<'a>(guess: &str, solutions: &'a [String], found: &[String]) -> Option<&'a str> {
+    let guess = guess.to_uppercase();
+    let len = guess.chars().count();
+    let max = max_distance(len);
+    let mut best: Option<(usize, &str)> = None;
+    for word in solutions.iter() {
+        if *word == guess || found.contains(word) {
+            continue;
+        }
+        // can't be close if the lengths are too far apart
+        let word_len = word.chars().count();
+        if word_len + max < len || len + max < word_len {
+            continue;
+        }
+        let distance = edit_distance(&guess, word);
+        if distance > max {
+            continue;
+        }
+        match best {
+            Some((d, _)) if d <= distance => (),
+            _ => best = Some((distance, word)),
+        }
+    }
+    best.map(|(_, word)| word)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(list: &[&str]) -> Vec<String> {
+        list.iter().map(|w| w.to_string()).collect()
+    }
+
+    #[test]
+    fn edit_distance_counts_slips() {
+        assert_eq!(edit_distance("CAT", "CAT"), 0);
+        assert_eq!(edit_distance("CAT", "CATS"), 1);
+        assert_eq!(edit_distance("CST", "CAT"), 1);
+        assert_eq!(edit_distance("CT", "CAT"), 1);
+        assert_eq!(edit_distance("DGO", "DOG"), 1);
+        assert_eq!(edit_distance("", "DOG"), 3);
+        assert_eq!(edit_distance("KITTEN", "SITTING"), 3);
+    }
+
+    #[test]
+    fn closest_picks_the_nearest_word_left() {
+        let solutions = words(&["CAT", "CATS", "DOG", "GO", "TAC"]);
+        assert_eq!(closest("cst", &solutions, &[]), Some("CAT"));
+        assert_eq!(closest("cots", &solutions, &[]), Some("CATS"));
+        // CAT is already found, CATS is next closest
+        assert_eq!(closest("cat", &solutions, &words(&["CAT"])), Some("CATS"));
+        assert_eq!(closest("dgo", &solutions, &[]), Some("DOG"));
+        // DOG is two slips away, too many for a short word
+        assert_eq!(closest("dxgo", &solutions, &[]), None);
+        assert_eq!(closest("zebra", &solutions, &[]), None);
+    }
+}
@@ -1,33 +1,55 @@
-// extract some of the logic for getting the user's playing time,
-// getting remaining time, and checking if time is up
+//! extract some of the logic for setting the playing time,
+//! getting remaining time, and checking if time is up
 
-use std::time::SystemTime;
-use std::io;
+use std::time::Duration;
 
-pub struct Timer {
-    start_time: SystemTime,
+use clock::{Clock, SystemClock};
+
+pub struct Timer<C = SystemClock> {
+    clock: C,
+    start_time: Duration,
+    // when the timer was paused, if it is paused
+    paused_at: Option<Duration>,
+    // played before this timer took over, e.g. before a round was saved
+    played_before: u64,
     max_time_secs: i64,
     pub max_time_minutes: f64,
 }
 
+impl Default for Timer {
+    fn default() -> Timer {
+        Timer::new()
+    }
+}
+
 impl Timer {
     pub fn new() -> Timer {
+        Timer::with_clock(SystemClock::new())
+    }
+}
+
+impl<C: Clock> Timer<C> {
+    pub fn with_clock(clock: C) -> Timer<C> {
         Timer {
-            start_time: SystemTime::now(),
+            start_time: clock.now(),
+            clock,
+            paused_at: None,
+            played_before: 0,
             max_time_secs: 0,
             max_time_minutes: 0.0,
         }
     }
 
     pub fn get_remaining_time(&self) -> i64 {
-        match self.start_time.elapsed() {
-            Ok(elapsed) => self.max_time_secs - elapsed.as_secs() as i64,
-            // If clock goes backwards, alert user
-            Err(e) => {
-                println!("Error: {:?}", e);
-                -1
-            }
-        }
+        self.max_time_secs - self.elapsed_secs() as i64
+    }
+
+    // seconds played since the start, not counting time spent paused
+    pub fn elapsed_secs(&self) -> u64 {
+        // time stands still while paused
+        let now = self.paused_at.unwrap_or_else(|| self.clock.now());
+        let played = now.checked_sub(self.start_time).unwrap_or_default().as_secs();
+        self.played_before + played
     }
 
     pub fn is_time_up(&self) -> bool {
@@ -35,28 +57,100 @@ impl Timer {
     }
 
     pub fn start(&mut self) {
-        self.start_time = SystemTime::now()
-    }
-
-    pub fn get_user_play_time(&mut self) {
-        // get total game length
-        let mut max_time_minutes = String::new();
-        loop {
-            io::stdin().read_line(&mut max_time_minutes).expect(
-                "Failed to read line",
-            );
-            match max_time_minutes.trim().parse::<f64>() {
-                Ok(n) => {
-                    self.max_time_minutes = n;
-                    self.max_time_secs = (n * 60.0) as i64;
-                    break;
-                }
-                Err(_e) => {
-                    println!("Enter a number!");
-                    // great, now we have to clear play_time_minutes and start_time again...
-                    max_time_minutes = String::new();
-                }
-            }
+        self.start_time = self.clock.now();
+        self.paused_at = None;
+    }
+
+    pub fn pause(&mut self) {
+        if self.paused_at.is_none() {
+            self.paused_at = Some(self.clock.now());
         }
     }
+
+    // the time spent paused doesn't count against the player
+    pub fn resume(&mut self) {
+        if let Some(paused_at) = self.paused_at.take() {
+            let paused_for = self.clock.now().checked_sub(paused_at).unwrap_or_default();
+            self.start_time += paused_for;
+        }
+    }
+
+    // count `secs` as already played, and have `left` of them remaining.
+    // that's how a saved round picks up where it was left
+    pub fn carry_on(&mut self, secs: u64, left: u64) {
+        self.played_before = secs;
+        self.max_time_secs = (secs + left) as i64;
+    }
+
+    // take time off the clock, e.g. as the price of a hint
+    pub fn deduct(&mut self, secs: u64) {
+        self.max_time_secs -= secs as i64;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused_at.is_some()
+    }
+
+    pub fn set_play_time(&mut self, minutes: f64) {
+        self.max_time_minutes = minutes;
+        self.max_time_secs = (minutes * 60.0) as i64;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clock::ManualClock;
+
+    #[test]
+    fn remaining_time_follows_the_clock() {
+        let clock = ManualClock::new();
+        let mut timer = Timer::with_clock(clock.clone());
+        timer.set_play_time(0.5);
+        clock.advance(Duration::from_secs(100));
+        timer.start();
+        assert_eq!(timer.get_remaining_time(), 30);
+
+        clock.advance(Duration::from_millis(29_500));
+        assert_eq!(timer.get_remaining_time(), 1);
+        assert!(!timer.is_time_up());
+
+        clock.advance(Duration::from_millis(500));
+        assert!(timer.is_time_up());
+    }
+
+    #[test]
+    fn carrying_on_keeps_the_time_that_was_left() {
+        let clock = ManualClock::new();
+        let mut timer = Timer::with_clock(clock.clone());
+        timer.set_play_time(3.0);
+        timer.carry_on(100, 50);
+        clock.advance(Duration::from_secs(3600));
+        timer.start();
+        assert_eq!(timer.elapsed_secs(), 100);
+        assert_eq!(timer.get_remaining_time(), 50);
+        clock.advance(Duration::from_secs(20));
+        assert_eq!(timer.elapsed_secs(), 120);
+        assert_eq!(timer.get_remaining_time(), 30);
+    }
+
+    #[test]
+    fn paused_time_does_not_count() {
+        let clock = ManualClock::new();
+        let mut timer = Timer::with_clock(clock.clone());
+        timer.set_play_time(1.0);
+        timer.start();
+        clock.advance(Duration::from_secs(10));
+
+        timer.pause();
+        assert!(timer.is_paused());
+        clock.advance(Duration::from_secs(600));
+        assert_eq!(timer.get_remaining_time(), 50);
+
+        timer.resume();
+        assert!(!timer.is_paused());
+        assert_eq!(timer.get_remaining_time(), 50);
+        clock.advance(Duration::from_secs(20));
+        assert_eq!(timer.get_remaining_time(), 30);
+    }
 }
@@ -0,0 +1,69 @@
+extern crate boggle;
+
+mod common;
+
+use std::sync::Arc;
+
+use boggle::{solver, Board, BoardError, Dictionary, Game, Player, Rejection};
+
+fn faces(letters: &str) -> Vec<String> {
+    letters.chars().map(|c| c.to_string()).collect()
+}
+
+fn game() -> Game {
+    let board = common::board();
+    let dictionary = Dictionary::from_words(vec!["cat", "cats", "dog", "act", "go"]);
+    Game::new(board, Player::new(String::from("eric")), Some(Arc::new(dictionary)))
+}
+
+#[test]
+fn board_can_be_inspected() {
+    let game = game();
+    assert_eq!(game.board.rows(), 4);
+    assert_eq!(game.board.cols(), 4);
+    assert_eq!(game.board.get(2, 1).map(|p| p.letter()), Some('O'));
+    assert_eq!(game.board.find_paths("dog"), vec![vec![(2, 0), (2, 1), (2, 2)]]);
+    assert_eq!(game.board.iter().count(), 16);
+}
+
+#[test]
+fn invalid_boards_are_rejected() {
+    match Board::try_from_letters(4, 4, &faces("CATS")) {
+        Err(BoardError::WrongLength { expected: 16, actual: 4 }) => (),
+        other => panic!("unexpected result {:?}", other),
+    }
+}
+
+#[test]
+fn solver_finds_all_playable_words() {
+    let game = game();
+    let words = solver::solve(&game.board, game.dictionary.as_ref().unwrap());
+    assert_eq!(words, vec!["CAT", "CATS", "DOG", "GO"]);
+}
+
+#[test]
+fn guesses_are_scored_into_buckets() {
+    let mut game = game();
+    assert_eq!(game.add_guess(String::from("cats")), Ok(1));
+    assert_eq!(game.add_guess(String::from("dog")), Ok(1));
+    assert_eq!(game.add_guess(String::from("dog")), Err(Rejection::Duplicate));
+    assert_eq!(game.add_guess(String::from("tac")), Err(Rejection::NotInDictionary));
+    assert!(game.add_guess(String::from("zebra")).is_err());
+
+    let words = |guesses: &[boggle::Guess]| -> Vec<String> {
+        guesses.iter().map(|g| g.word().to_string()).collect()
+    };
+    assert_eq!(words(game.guesses.valid()), vec!["cats", "dog"]);
+    assert_eq!(words(game.guesses.not_in_dict()), vec!["tac"]);
+    assert_eq!(words(game.guesses.invalid()), vec!["zebra"]);
+    assert_eq!(game.guesses.score(), 2);
+}
+
+#[test]
+fn timer_reports_remaining_time() {
+    let mut timer = boggle::Timer::new();
+    timer.set_play_time(0.5);
+    timer.start();
+    assert!(!timer.is_time_up());
+    assert!(timer.get_remaining_time() <= 30);
+}
@@ -0,0 +1,7 @@
+use boggle::Board;
+
+// CATS across the top and DOG on the third row, the board most tests play on
+pub fn board() -> Board {
+    let faces: Vec<String> = "CATSXXXXDOGXXXXX".chars().map(|c| c.to_string()).collect();
+    Board::try_from_letters(4, 4, &faces).unwrap()
+}
@@ -0,0 +1,183 @@
+extern crate boggle;
+
+mod common;
+
+use std::io::{BufRead, BufReader, Cursor, Write};
+use std::net::TcpStream;
+use std::thread;
+use std::time::Duration;
+
+use boggle::net::{Client, Server};
+use boggle::{Console, Dictionary};
+
+use common::board;
+
+#[test]
+fn host_checks_guesses_and_cancels_shared_words() {
+    let dictionary = Dictionary::from_words(vec!["cat", "cats", "dog"]);
+    let mut server = Server::bind("127.0.0.1:0", Some(dictionary)).unwrap();
+    let addr = server.local_addr().unwrap();
+    let host = thread::spawn(move || {
+        server.wait_for_players(2).unwrap();
+        // a little over a second
+        server.play_round(board(), 0.02).unwrap()
+    });
+
+    // a bare socket speaking the protocol by hand
+    let mut raw = TcpStream::connect(addr).unwrap();
+    raw.write_all(b"{\"type\":\"hello\",\"version\":1,\"name\":\"bot\"}\n").unwrap();
+    let mut from_host = BufReader::new(raw.try_clone().unwrap());
+    let mut line = String::new();
+    from_host.read_line(&mut line).unwrap();
+    assert_eq!(line, "{\"type\":\"hello\",\"version\":1,\"name\":\"bot\"}\n");
+
+    let client = Client::connect(addr, "eric").unwrap();
+    let input = Cursor::new(b"cats\ndog\nzebra\n".to_vec());
+    let player = thread::spawn(move || {
+        let mut console = Console::plain(vec![]);
+        client.play(input, &mut console).unwrap();
+        String::from_utf8(console.get_ref().clone()).unwrap()
+    });
+
+    line.clear();
+    from_host.read_line(&mut line).unwrap();
+    assert_eq!(
+        line,
+        "{\"type\":\"board\",\"board\":\"4x4:CATSXXXXDOGXXXXX\",\"seed\":null,\"seconds\":1}\n"
+    );
+    line.clear();
+    from_host.read_line(&mut line).unwrap();
+    assert_eq!(line, "{\"type\":\"start\"}\n");
+
+    raw.write_all(b"{\"type\":\"guess\",\"word\":\"dog\"}\n").unwrap();
+    line.clear();
+    from_host.read_line(&mut line).unwrap();
+    assert_eq!(
+        line,
+        "{\"type\":\"verdict\",\"word\":\"dog\",\"points\":1,\"rejection\":null,\"reason\":null,\"score\":1}\n"
+    );
+
+    let cancellation = host.join().unwrap();
+    assert_eq!(cancellation.players, vec!["bot", "eric"]);
+    // dog was found by both, so only eric's cats counts
+    assert_eq!(cancellation.scores, vec![0, 1]);
+
+    let transcript = player.join().unwrap();
+    assert!(transcript.contains("\"cats\" +1 (score 1)\n"));
+    assert!(transcript.contains("\"zebra\" doesn't count: the letter Z is not on the board\n"));
+    assert!(transcript.contains("Time's up!\n"));
+    assert!(transcript.contains("Only eric found: cats\n"));
+    assert!(transcript.contains("dog    x    x\n"));
+}
+
+#[test]
+fn host_renames_twins_and_turns_away_other_versions() {
+    let mut server = Server::bind("127.0.0.1:0", None).unwrap();
+    let addr = server.local_addr().unwrap();
+    let host = thread::spawn(move || {
+        server.wait_for_players(2).unwrap();
+        server.players().iter().map(|p| p.to_string()).collect::<Vec<_>>()
+    });
+
+    let mut stranger = TcpStream::connect(addr).unwrap();
+    stranger
+        .write_all(b"{\"type\":\"hello\",\"version\":99,\"name\":\"eric\"}\n")
+        .unwrap();
+    let mut line = String::new();
+    BufReader::new(stranger).read_line(&mut line).unwrap();
+    assert_eq!(
+        line,
+        "{\"type\":\"error\",\"message\":\"this host speaks version 1 of the protocol, not 99\"}\n"
+    );
+
+    let first = Client::connect(addr, "eric").unwrap();
+    let second = Client::connect(addr, "Eric").unwrap();
+    assert_eq!(first.name(), "eric");
+    assert_eq!(second.name(), "Eric 2");
+    assert_eq!(host.join().unwrap(), vec!["eric", "Eric 2"]);
+}
+
+#[test]
+fn a_word_in_another_case_is_a_duplicate() {
+    let dictionary = Dictionary::from_words(vec!["cat"]);
+    let mut server = Server::bind("127.0.0.1:0", Some(dictionary)).unwrap();
+    let addr = server.local_addr().unwrap();
+    let host = thread::spawn(move || {
+        server.wait_for_players(1).unwrap();
+        server.play_round(board(), 0.02).unwrap()
+    });
+
+    let mut raw = TcpStream::connect(addr).unwrap();
+    raw.write_all(b"{\"type\":\"hello\",\"version\":1,\"name\":\"bot\"}\n").unwrap();
+    raw.write_all(b"{\"type\":\"guess\",\"word\":\"cat\"}\n").unwrap();
+    raw.write_all(b"{\"type\":\"guess\",\"word\":\"CAT\"}\n").unwrap();
+    let verdicts: Vec<String> = BufReader::new(raw)
+        .lines()
+        .map(|line| line.unwrap())
+        .take_while(|line| !line.starts_with("{\"type\":\"results\""))
+        .filter(|line| line.starts_with("{\"type\":\"verdict\""))
+        .collect();
+    assert_eq!(
+        verdicts,
+        vec![
+            "{\"type\":\"verdict\",\"word\":\"cat\",\"points\":1,\"rejection\":null,\"reason\":null,\"score\":1}",
+            "{\"type\":\"verdict\",\"word\":\"CAT\",\"points\":0,\"rejection\":{\"kind\":\"duplicate\"},\"reason\":\"you already found it\",\"score\":1}",
+        ]
+    );
+    assert_eq!(host.join().unwrap().scores, vec![1]);
+}
+
+#[test]
+fn a_silent_connection_doesnt_keep_players_out() {
+    let mut server = Server::bind("127.0.0.1:0", None).unwrap();
+    server.set_hello_timeout(Duration::from_millis(200));
+    let addr = server.local_addr().unwrap();
+    let host = thread::spawn(move || {
+        server.wait_for_players(1).unwrap();
+        server.players().iter().map(|p| p.to_string()).collect::<Vec<_>>()
+    });
+
+    // connects, then says nothing
+    let _silent = TcpStream::connect(addr).unwrap();
+    let client = Client::connect(addr, "eric").unwrap();
+    assert_eq!(client.name(), "eric");
+    assert_eq!(host.join().unwrap(), vec!["eric"]);
+}
+
+#[test]
+fn an_overlong_line_gets_a_player_dropped() {
+    let dictionary = Dictionary::from_words(vec!["cat", "dog"]);
+    let mut server = Server::bind("127.0.0.1:0", Some(dictionary)).unwrap();
+    let addr = server.local_addr().unwrap();
+    let host = thread::spawn(move || {
+        server.wait_for_players(2).unwrap();
+        server.play_round(board(), 0.02).unwrap()
+    });
+
+    let mut raw = TcpStream::connect(addr).unwrap();
+    raw.write_all(b"{\"type\":\"hello\",\"version\":1,\"name\":\"bot\"}\n").unwrap();
+    raw.write_all(b"{\"type\":\"guess\",\"word\":\"cat\"}\n").unwrap();
+    let client = Client::connect(addr, "eric").unwrap();
+    let player = thread::spawn(move || {
+        let mut console = Console::plain(vec![]);
+        client.play(Cursor::new(b"dog\n".to_vec()), &mut console).unwrap();
+        String::from_utf8(console.get_ref().clone()).unwrap()
+    });
+
+    // one word that never ends. a host that put up with it would send
+    // the results in a second, this gives up on it well after that
+    raw.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+    let mut from_host = BufReader::new(raw.try_clone().unwrap());
+    let mut line = String::new();
+    while !line.starts_with("{\"type\":\"verdict\"") {
+        line.clear();
+        from_host.read_line(&mut line).unwrap();
+    }
+    let _ = raw.write_all(&[b'x'; 10_000]);
+    let rest: Vec<String> = from_host.lines().map_while(Result::ok).collect();
+    assert!(!rest.iter().any(|line| line.starts_with("{\"type\":\"results\"")));
+
+    // the round goes on without them, with what they found before
+    assert_eq!(host.join().unwrap().scores, vec![1, 1]);
+    assert!(player.join().unwrap().contains("Only eric found: dog\n"));
+}
@@ -0,0 +1,793 @@
+extern crate boggle;
+
+mod common;
+
+use std::collections::VecDeque;
+use std::env;
+use std::fs;
+use std::io::{self, BufRead, Read};
+use std::process;
+use std::sync::Arc;
+use std::time::Duration;
+
+use boggle::export::Export;
+use boggle::journal;
+use boggle::save::SavedGame;
+use boggle::{ChannelInput, Console, Dictionary, GameSession, History, InputEvent, ManualClock,
+             ProfileStore, Rules};
+
+use common::board;
+
+// input that lets `secs` pass on the clock before each line is typed
+struct Script {
+    clock: ManualClock,
+    lines: VecDeque<(u64, String)>,
+    current: Vec<u8>,
+    pos: usize,
+}
+
+impl Script {
+    fn new(clock: &ManualClock, lines: &[(u64, &str)]) -> Script {
+        Script {
+            clock: clock.clone(),
+            lines: lines
+                .iter()
+                .map(|&(secs, line)| (secs, format!("{}\n", line)))
+                .collect(),
+            current: vec![],
+            pos: 0,
+        }
+    }
+}
+
+impl Read for Script {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = {
+            let available = self.fill_buf()?;
+            let n = available.len().min(buf.len());
+            buf[..n].copy_from_slice(&available[..n]);
+            n
+        };
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl BufRead for Script {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.pos >= self.current.len() {
+            if let Some((secs, line)) = self.lines.pop_front() {
+                self.clock.advance(Duration::from_secs(secs));
+                self.current = line.into_bytes();
+                self.pos = 0;
+            }
+        }
+        Ok(&self.current[self.pos..])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.pos += amt;
+    }
+}
+
+fn session(
+    clock: &ManualClock,
+    lines: &[(u64, &str)],
+    dictionary: Option<Dictionary>,
+) -> GameSession<Script, Vec<u8>, ManualClock> {
+    let input = Script::new(clock, lines);
+    let mut session = GameSession::new(input, Console::plain(vec![]), clock.clone(), dictionary);
+    session.use_board(board());
+    session
+}
+
+fn transcript(session: &GameSession<Script, Vec<u8>, ManualClock>) -> String {
+    String::from_utf8(session.output().clone()).unwrap()
+}
+
+#[test]
+fn plays_a_scripted_round() {
+    let clock = ManualClock::new();
+    let dictionary = Dictionary::from_words(vec!["cat", "cats", "dog"]);
+    let mut session = session(
+        &clock,
+        &[
+            (0, "0.5"),
+            (0, "eric"),
+            (5, "cats"),
+            (5, "tac"),
+            (5, "zebra"),
+            (20, "dog"),
+            (0, "q"),
+        ],
+        Some(dictionary),
+    );
+    session.run().unwrap();
+    let game = &session.games()[0];
+
+    assert_eq!(game.player.name, "eric");
+    assert_eq!(game.guesses.valid().len(), 1);
+    assert_eq!(game.guesses.not_in_dict().len(), 1);
+    assert_eq!(game.guesses.invalid().len(), 1);
+    assert_eq!(game.guesses.score(), 1);
+
+    let transcript = transcript(&session);
+    assert!(transcript.starts_with("Welcome to Boggle®\n"));
+    assert!(!transcript.contains("WARNING"));
+    assert!(transcript.contains("Hello eric, here is your game:"));
+    assert!(transcript.contains("Enter as many words as possible in 0.5 mins!"));
+    assert!(transcript.contains(" C  A  T  S \n"));
+    assert!(transcript.contains("Now start typing words! (30 seconds left)"));
+    assert!(transcript.contains("Now start typing words! (15 seconds left)"));
+    assert!(transcript.contains("\"dog\" was entered after time ran out"));
+    assert!(transcript.contains("\nValid words\n=============\ncats\n"));
+    assert!(transcript.contains("Total Score: 1\n"));
+    assert!(transcript.ends_with("Thanks for playing!\n"));
+}
+
+#[test]
+fn asks_again_for_a_number() {
+    let clock = ManualClock::new();
+    let mut session = session(
+        &clock,
+        &[(0, "soon"), (0, "1"), (0, "eric"), (61, "late")],
+        None,
+    );
+    session.run().unwrap();
+
+    let transcript = transcript(&session);
+    assert!(transcript.contains("WARNING: There was no dictionary file available!"));
+    assert!(transcript.contains("Enter a number!\n"));
+    assert!(transcript.contains("Enter as many words as possible in 1 mins!"));
+}
+
+#[test]
+fn end_of_input_ends_the_round() {
+    let clock = ManualClock::new();
+    let mut session = session(&clock, &[(0, "1"), (0, "eric"), (1, "cats")], None);
+    session.run().unwrap();
+
+    assert_eq!(session.games().len(), 1);
+    assert_eq!(session.games()[0].guesses.valid().len(), 1);
+    let transcript = transcript(&session);
+    assert!(transcript.contains("Nice job! Here are your results:"));
+    assert!(transcript.contains("Total Score: 1\n"));
+    // and the menu quits at the end of input too
+    assert!(transcript.ends_with("Thanks for playing!\n"));
+}
+
+#[test]
+fn end_of_input_during_setup_is_an_error() {
+    let clock = ManualClock::new();
+    let mut session = session(&clock, &[(0, "soon")], None);
+    match session.run() {
+        Err(boggle::Error::Eof) => (),
+        other => panic!("expected Eof, got {:?}", other.map(|_| ())),
+    }
+    // does not keep asking for a number forever
+    assert_eq!(transcript(&session).matches("Enter a number!").count(), 1);
+}
+
+#[test]
+fn menu_replays_and_changes_settings() {
+    let clock = ManualClock::new();
+    let dictionary = Dictionary::from_words(vec!["cat", "cats", "dog"]);
+    let mut session = session(
+        &clock,
+        &[
+            (0, "1"),
+            (0, "eric"),
+            (1, "cat"),
+            (60, "late"),
+            // same board again
+            (0, "r"),
+            (1, "cats"),
+            (1, "dog"),
+            (60, "late"),
+            (0, "huh"),
+            (0, "s"),
+            // settings only, no game
+            (0, "o"),
+            (0, "0.5"),
+            (0, "3x5"),
+            // a new random board with the new settings
+            (0, "a"),
+            (31, "late"),
+            (0, "q"),
+        ],
+        Some(dictionary),
+    );
+    session.run().unwrap();
+
+    let games = session.games();
+    assert_eq!(games.len(), 3);
+    assert_eq!(games[0].board.letters(), games[1].board.letters());
+    assert_eq!(games[0].guesses.score(), 1);
+    assert_eq!(games[1].guesses.score(), 2);
+    assert_eq!(games[1].player.name, "eric");
+    assert_eq!(games[2].board.rows(), 3);
+    assert_eq!(games[2].board.cols(), 5);
+    assert_eq!(session.settings().minutes, 0.5);
+
+    let transcript = transcript(&session);
+    assert!(transcript.contains("Pick one of n, a, r, h, c, s, o or q"));
+    assert!(transcript.contains("Games played: 2\nBest score: 2\nAverage score: 1.5\nLongest word: cats\n"));
+    assert!(transcript.contains("Enter as many words as possible in 0.5 mins!"));
+}
+
+#[test]
+fn new_game_asks_for_settings() {
+    let clock = ManualClock::new();
+    let mut session = session(
+        &clock,
+        &[
+            (0, "1"),
+            (0, "eric"),
+            (61, "late"),
+            (0, "n"),
+            (0, "2"),
+            (0, "big"),
+            (0, ""),
+            (121, "late"),
+        ],
+        None,
+    );
+    session.run().unwrap();
+
+    assert_eq!(session.games().len(), 2);
+    assert_eq!(session.games()[1].board.rows(), 4);
+    let transcript = transcript(&session);
+    assert!(transcript.contains("Enter a size like 4x4, up to 10x10!"));
+    assert!(transcript.contains("Enter as many words as possible in 2 mins!"));
+}
+
+#[test]
+fn commands_during_a_round() {
+    let clock = ManualClock::new();
+    let dictionary = Dictionary::from_words(vec!["cat", "cats", "dog", "go"]);
+    let mut session = session(
+        &clock,
+        &[
+            (0, "1"),
+            (0, "eric"),
+            (1, ":list"),
+            (1, "cat"),
+            (1, "dog"),
+            (1, ":score"),
+            (1, ":undo"),
+            (1, ":list"),
+            (1, ":hint lengths"),
+            (1, ":time"),
+            (1, ":pause"),
+            (300, ""),
+            (1, ":time"),
+            (1, ":shuffle"),
+            (1, ":nope"),
+            (1, ":help"),
+            (1, ""),
+            (1, ":quit"),
+            (0, "q"),
+        ],
+        Some(dictionary),
+    );
+    session.run().unwrap();
+
+    let game = &session.games()[0];
+    assert_eq!(game.guesses.valid().len(), 1);
+    assert!(game.guesses.invalid().is_empty(), "commands or blank lines counted as guesses");
+    assert_eq!(game.guesses.score(), 1);
+
+    let transcript = transcript(&session);
+    assert!(transcript.contains("No words yet\n"));
+    assert!(transcript.contains("Score so far: 2\n"));
+    assert!(transcript.contains("Took back \"dog\"\n"));
+    assert!(transcript.contains("Words so far: cat\n"));
+    assert!(transcript.contains("Words left to find: 1 with 2 letters, 1 with 3 letters, 1 with 4 letters\n"));
+    assert!(transcript.contains("52 seconds left\n"));
+    assert!(transcript.contains("Paused with 51 seconds left"));
+    assert!(transcript.contains("50 seconds left\n"));
+    assert!(transcript.contains(" X  D  X  C \n"));
+    assert!(transcript.contains("Unknown command \":nope\", type :help for a list\n"));
+    assert!(transcript.contains("  :undo     take back your last guess\n"));
+}
+
+#[test]
+fn hints_cost_what_the_rules_say() {
+    let clock = ManualClock::new();
+    let dictionary = Dictionary::from_words(vec!["cat", "cats", "dog"]);
+    let mut session = session(
+        &clock,
+        &[
+            (0, "1"),
+            (0, "eric"),
+            (0, "cats"),
+            (0, "cat"),
+            (0, ":hint tile"),
+            (0, ":time"),
+            (0, ":hint longest"),
+            (0, "dog"),
+            (0, ":hint"),
+            (0, ":quit"),
+            (0, "q"),
+        ],
+        Some(dictionary),
+    );
+    session.set_rules(Rules {
+        hint_points: 1,
+        hint_seconds: 10,
+        ..Rules::default()
+    });
+    session.run().unwrap();
+
+    let game = &session.games()[0];
+    assert_eq!(game.guesses.penalty(), 2);
+    assert_eq!(game.guesses.score(), 1);
+
+    let transcript = transcript(&session);
+    assert!(transcript.contains("\n C  A  T  S \n\n X  X  X  X \n\n[D] O  G  X \n"));
+    assert!(transcript.contains("There is a word starting on the marked tile\n"));
+    assert!(transcript.contains("That hint cost you 1 points\nThat hint cost you 10 seconds\n"));
+    assert!(transcript.contains("50 seconds left\n"));
+    assert!(transcript.contains("There is a 3-letter word here\n"));
+    // nothing left to hint at, so no charge
+    assert!(transcript.contains("You found every word, there's nothing left to hint at!\n"));
+    assert!(transcript.contains("Hint penalty: -2\nTotal Score: 1\n"));
+}
+
+#[test]
+fn hints_skip_words_too_short_to_count() {
+    let clock = ManualClock::new();
+    let dictionary = Dictionary::from_words(vec!["cat", "cats", "dog"]);
+    let lines = [(0, "1"), (0, "eric"), (0, "cats"), (0, ":hint"), (0, ":quit"), (0, "q")];
+    let mut session = session(&clock, &lines, Some(dictionary));
+    session.set_rules(Rules {
+        hint_points: 1,
+        min_word_length: 4,
+        ..Rules::default()
+    });
+    session.run().unwrap();
+
+    // cat and dog don't count, so there's nothing to hint at or pay for
+    assert!(transcript(&session)
+        .contains("You found every word, there's nothing left to hint at!\n"));
+    assert_eq!(session.games()[0].guesses.penalty(), 0);
+}
+
+#[test]
+fn hints_need_a_dictionary() {
+    let clock = ManualClock::new();
+    let mut session = session(
+        &clock,
+        &[(0, "1"), (0, "eric"), (0, ":hint"), (0, ":quit"), (0, "q")],
+        None,
+    );
+    session.run().unwrap();
+    assert!(transcript(&session).contains("Hints need a dictionary\n"));
+}
+
+#[test]
+fn rejected_guesses_say_why() {
+    let clock = ManualClock::new();
+    let dictionary = Dictionary::from_words(vec!["cat", "cats", "dog", "act"]);
+    let mut session = session(
+        &clock,
+        &[
+            (0, "3"),
+            (0, "eric"),
+            (0, "cat"),
+            (0, "cat"),
+            (0, "zebra"),
+            (0, "cad"),
+            (0, "tat"),
+            (0, "cog"),
+            (0, ":quit"),
+            (0, "q"),
+        ],
+        Some(dictionary),
+    );
+    session.run().unwrap();
+
+    let transcript = transcript(&session);
+    assert!(transcript.contains("\"cat\" doesn't count: you already found it\n"));
+    assert!(transcript.contains("\"zebra\" doesn't count: the letter Z is not on the board\n"));
+    assert!(transcript.contains("\"cad\" doesn't count: letter 3 (D) doesn't touch the letters before it\n"));
+    assert!(transcript.contains("\"tat\" doesn't count: it would use the tile at row 1, column 3 twice\n"));
+    assert!(transcript.contains(
+        "\"cog\" doesn't count: letter 2 (O) doesn't touch the letters before it. \
+         Did you mean \"dog\"?\n"
+    ));
+    assert!(transcript.contains(
+        "cog (letter 2 (O) doesn't touch the letters before it; did you mean \"dog\"?)\n"
+    ));
+    assert!(transcript.contains("Invalid words\n=============\nzebra (the letter Z is not on the board)\n"));
+    assert!(transcript.contains("Repeated words\n=============\ncat\n"));
+}
+
+#[test]
+fn practice_takes_a_suggestion_on_enter() {
+    let clock = ManualClock::new();
+    let dictionary = Dictionary::from_words(vec!["cat", "cats", "dog"]);
+    let mut session = session(
+        &clock,
+        &[
+            (0, "3"),
+            (0, "eric"),
+            (0, "dgo"),
+            (0, ""),
+            (0, "cst"),
+            (0, "dog"),
+            (0, ""),
+            (0, ":quit"),
+            (0, "q"),
+        ],
+        Some(dictionary),
+    );
+    session.set_rules(Rules {
+        practice: true,
+        ..Rules::default()
+    });
+    session.run().unwrap();
+
+    let game = &session.games()[0];
+    let valid: Vec<&str> = game.guesses.valid().iter().map(|g| g.word()).collect();
+    // typing something else passes up the offer for "cat"
+    assert_eq!(valid, vec!["dog"]);
+    assert_eq!(game.guesses.invalid()[0].word(), "cst");
+    assert_eq!(game.guesses.score(), 1);
+
+    let transcript = transcript(&session);
+    assert!(transcript.contains("Did you mean \"dog\"? (press enter to take it)\n"));
+    assert!(transcript.contains("Took \"dog\" instead\n"));
+}
+
+#[test]
+fn finished_rounds_are_saved() {
+    let path = env::temp_dir().join(format!("boggle-session-history-{}.jsonl", process::id()));
+    let _ = fs::remove_file(&path);
+    let clock = ManualClock::new();
+    let mut session = session(
+        &clock,
+        &[(0, "1"), (0, "eric"), (5, "cats"), (10, "zebra"), (0, ":quit"), (0, "q")],
+        None,
+    );
+    session.set_history(History::new(path.clone()));
+    session.run().unwrap();
+
+    let records = History::new(path.clone()).load().unwrap();
+    fs::remove_file(&path).unwrap();
+    assert_eq!(records.len(), 1);
+    let record = &records[0];
+    assert_eq!(record.player, "eric");
+    assert_eq!(record.board, "4x4:CATSXXXXDOGXXXXX");
+    assert_eq!(record.minutes, 1.0);
+    assert_eq!(record.seconds_played, 15);
+    assert_eq!(record.score, 1);
+    let guesses: Vec<(&str, Option<u64>)> = record
+        .guesses
+        .iter()
+        .map(|g| (g.word.as_str(), g.at_secs))
+        .collect();
+    assert_eq!(guesses, vec![("cats", Some(5)), ("zebra", Some(15))]);
+}
+
+#[test]
+fn rounds_are_written_to_the_output_file() {
+    let path = env::temp_dir().join(format!("boggle-session-output-{}.csv", process::id()));
+    let clock = ManualClock::new();
+    let dictionary = Dictionary::from_words(vec!["cat", "cats", "dog"]);
+    let mut session = session(
+        &clock,
+        &[
+            (0, "1"),
+            (0, "eric"),
+            (5, "cats"),
+            (10, "zebra"),
+            (0, ":quit"),
+            (0, "r"),
+            (3, "dog"),
+            (0, ":quit"),
+            (0, "q"),
+        ],
+        Some(dictionary),
+    );
+    session.set_export(Export::new(path.clone()).unwrap());
+    session.run().unwrap();
+
+    let csv = fs::read_to_string(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    let rows: Vec<Vec<&str>> = csv.lines().skip(1).map(|l| l.split(',').collect()).collect();
+    // round, word, bucket and seconds in of every row
+    let words: Vec<(&str, &str, &str, &str)> = rows
+        .iter()
+        .map(|r| (r[0], r[11], r[12], r[15]))
+        .collect();
+    assert_eq!(
+        words,
+        vec![
+            ("1", "cats", "valid", "5"),
+            ("1", "zebra", "invalid", "15"),
+            ("1", "cat", "missed", ""),
+            ("1", "dog", "missed", ""),
+            ("2", "dog", "valid", "3"),
+            ("2", "cat", "missed", ""),
+            ("2", "cats", "missed", ""),
+        ]
+    );
+    assert!(rows.iter().all(|r| r[3] == "4x4:CATSXXXXDOGXXXXX" && r[2] == "eric"));
+}
+
+#[test]
+fn profiles_remember_players() {
+    let dir = env::temp_dir().join(format!("boggle-session-profiles-{}", process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    let store = || ProfileStore::new(dir.join("profiles.json"));
+
+    // nobody has played yet, so the name makes a new profile
+    let clock = ManualClock::new();
+    let mut first = session(
+        &clock,
+        &[(0, "eric"), (0, "2"), (0, "cats"), (0, ":quit"), (0, "q")],
+        None,
+    );
+    first.set_profiles(store());
+    first.run().unwrap();
+
+    let eric = store().find("Eric").unwrap().unwrap();
+    assert_eq!(eric.preferences.minutes, Some(2.0));
+    assert_eq!(eric.stats.games, 1);
+    assert_eq!(eric.stats.best_score, 1);
+
+    // picked by number, and the play time isn't asked again
+    let mut second = session(
+        &clock,
+        &[(0, "1"), (0, "dog"), (0, ":quit"), (0, "s"), (0, "q")],
+        None,
+    );
+    second.set_profiles(store());
+    second.run().unwrap();
+
+    let transcript = transcript(&second);
+    assert!(transcript.contains("Who's playing?\n  [1] eric (1 games, best 1)\n"));
+    assert!(!transcript.contains("For how many minutes"));
+    assert!(transcript.contains("Enter as many words as possible in 2 mins!"));
+    assert!(transcript.contains("All games for eric\n================\nGames played: 2\n"));
+    assert_eq!(second.games()[0].player.name, "eric");
+    assert_eq!(store().find("eric").unwrap().unwrap().stats.total_score, 2);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn hot_seat_players_share_a_board() {
+    let clock = ManualClock::new();
+    let mut session = session(
+        &clock,
+        &[
+            (0, "1"),
+            (0, "eric"),
+            (0, ":quit"),
+            (0, "h"),
+            (0, "1"),
+            (0, "3"),
+            (0, "ana"),
+            (0, "eric"),
+            (0, ""),
+            (0, ""),
+            (0, "cats"),
+            (0, "dog"),
+            (0, ":quit"),
+            (0, ""),
+            (0, ""),
+            (0, "cat"),
+            (0, "dog"),
+            (0, ":quit"),
+            (0, ""),
+            (0, ""),
+            // the clock runs out on this turn
+            (61, "dog"),
+            (0, ""),
+            (0, "q"),
+        ],
+        None,
+    );
+    session.use_board(board());
+    session.run().unwrap();
+
+    // the single-player round, then one game per player on the same board
+    let games = session.games();
+    assert_eq!(games.len(), 4);
+    let names: Vec<&str> = games[1..].iter().map(|g| g.player.name.as_str()).collect();
+    assert_eq!(names, vec!["ana", "eric", "Player 3"]);
+    assert!(games[1..].iter().all(|g| g.board.code() == games[1].board.code()));
+
+    let transcript = transcript(&session);
+    assert!(transcript.contains("Enter a number from 2 to 8!"));
+    assert!(transcript.contains("Pass to ana, press enter when you're ready"));
+    assert!(transcript.contains("That's it, eric! You scored 2"));
+    // dog was found twice, so it doesn't count for anyone
+    assert!(transcript.contains("Only ana found: cats\nOnly eric found: cat\n"));
+    assert!(transcript.contains("dog    x    x\n"));
+    assert!(transcript.contains(
+        "\nResults\n=======\n   Player    Words  Score\n \
+          1 ana           1      1\n \
+          1 eric          1      1\n \
+          3 Player 3      0      0\n\
+         \nIt's a tie between ana and eric!\n"
+    ));
+}
+
+#[test]
+fn the_computer_plays_the_same_board() {
+    let clock = ManualClock::new();
+    let dictionary = Dictionary::from_words(vec!["cat", "cats", "dog"]);
+    let mut session = session(
+        &clock,
+        &[
+            (0, "1"),
+            (0, "eric"),
+            (0, ":quit"),
+            (0, "c"),
+            (0, "impossible"),
+            (0, "hard"),
+            (0, "cats"),
+            // plenty of time for it to find all three
+            (61, "dog"),
+            (0, "q"),
+        ],
+        Some(dictionary),
+    );
+    session.use_board(board());
+    session.run().unwrap();
+
+    // the computer's round isn't kept as one of the player's
+    let games = session.games();
+    assert_eq!(games.len(), 2);
+    assert_eq!(games[1].guesses.score(), 1);
+
+    let transcript = transcript(&session);
+    assert!(transcript.contains("Type easy, medium or hard!"));
+    assert!(transcript.contains("(60 seconds left, the computer has found 0 words)"));
+    // cats was found by both, so only the computer's other two count
+    assert!(transcript.contains("Only Computer (hard) found: cat, dog\n"));
+    assert!(transcript.contains("eric found no words of their own\n"));
+    assert!(transcript.contains("\nComputer (hard) wins!"));
+}
+
+#[test]
+fn the_computer_needs_a_dictionary() {
+    let clock = ManualClock::new();
+    let mut session = session(
+        &clock,
+        &[(0, "1"), (0, "eric"), (0, ":quit"), (0, "c"), (0, "q")],
+        None,
+    );
+    session.run().unwrap();
+    assert!(transcript(&session).contains("The computer needs a dictionary to play against you"));
+}
+
+#[test]
+fn saved_rounds_carry_on_with_the_time_that_was_left() {
+    let dir = env::temp_dir().join(format!("boggle-session-save-{}", process::id()));
+    let path = dir.join("saved.json");
+    let dictionary = Dictionary::from_words(vec!["cat", "cats", "dog"]);
+    let clock = ManualClock::new();
+    let mut first = session(
+        &clock,
+        &[(0, "1"), (0, "eric"), (5, "cats"), (10, ":save"), (0, "q")],
+        Some(dictionary.clone()),
+    );
+    first.set_save_path(&path);
+    first.run().unwrap();
+    assert!(first.games().is_empty());
+    assert!(transcript(&first).contains("Saved with 45 seconds left"));
+
+    // an hour away doesn't count against the clock
+    let saved = SavedGame::load(&path).unwrap();
+    assert_eq!(saved.seconds_left, 45);
+    clock.advance(Duration::from_secs(3600));
+    let mut second = session(&clock, &[(5, "dog"), (0, ":quit"), (0, "q")], Some(dictionary));
+    second.set_save_path(&path);
+    second.resume(saved).unwrap();
+
+    let output = transcript(&second);
+    assert!(output.contains("Picking up where you left off, with 1 found so far"));
+    assert!(output.contains("(45 seconds left)"), "{}", output);
+    let game = &second.games()[0];
+    assert_eq!(game.player.name, "eric");
+    let found: Vec<(&str, Option<u64>)> = game
+        .guesses
+        .valid()
+        .iter()
+        .map(|g| (g.word(), g.at_secs()))
+        .collect();
+    assert_eq!(found, vec![("cats", Some(5)), ("dog", Some(20))]);
+    // the round is over, so the save is gone
+    assert!(!path.exists());
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn an_interrupted_round_is_saved() {
+    let path = env::temp_dir().join(format!("boggle-session-interrupt-{}.json", process::id()));
+    let (input, sender) = ChannelInput::new();
+    for event in [
+        InputEvent::Line(String::from("1\n")),
+        InputEvent::Line(String::from("eric\n")),
+        InputEvent::Line(String::from("cats\n")),
+        InputEvent::Interrupt,
+        InputEvent::Line(String::from("q\n")),
+    ] {
+        sender.send(event).unwrap();
+    }
+    let interrupted = input.interrupted();
+    let dictionary = Dictionary::from_words(vec!["cat", "cats", "dog"]);
+    let clock = ManualClock::new();
+    let mut session = GameSession::new(input, Console::plain(vec![]), clock, Some(dictionary));
+    session.use_board(board());
+    session.set_save_path(&path);
+    session.set_interrupted(interrupted);
+    session.run().unwrap();
+
+    // the results are still shown, and the round can be finished later
+    assert_eq!(session.games()[0].guesses.score(), 1);
+    let saved = SavedGame::load(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    assert_eq!(saved.seconds_left, 60);
+    assert_eq!(saved.game.guesses.len(), 1);
+}
+
+#[test]
+fn a_round_that_crashes_can_be_recovered_from_its_journal() {
+    let path = env::temp_dir().join(format!("boggle-session-journal-{}.jsonl", process::id()));
+    let dictionary = Dictionary::from_words(vec!["cat", "cats", "dog"]);
+    let clock = ManualClock::new();
+    let lines = [(0, "1"), (0, "eric"), (5, "cats"), (5, "dog"), (5, ":undo"), (5, "cat")];
+    // input that breaks partway through the round
+    let input = Script::new(&clock, &lines).chain(&b"\xff\n"[..]);
+    let mut crashing = GameSession::new(
+        input,
+        Console::plain(vec![]),
+        clock.clone(),
+        Some(dictionary.clone()),
+    );
+    crashing.use_board(board());
+    crashing.set_journal(&path);
+    assert!(crashing.run().is_err());
+
+    let recovered = journal::recover(&path, Some(Arc::new(dictionary.clone()))).unwrap();
+    let found: Vec<&str> = recovered.game.guesses.valid().iter().map(|g| g.word()).collect();
+    assert_eq!(found, vec!["cats", "cat"]);
+    assert_eq!(recovered.seconds_played, 20);
+    assert_eq!(recovered.game.player.name, "eric");
+
+    // a round that ends normally doesn't leave a journal behind
+    let lines = [(0, "1"), (0, "eric"), (5, "cats"), (0, ":quit"), (0, "q")];
+    let mut session = session(&clock, &lines, Some(dictionary));
+    session.set_journal(&path);
+    session.run().unwrap();
+    assert!(!path.exists());
+}
+
+#[test]
+fn an_interrupt_while_paused_saves_the_round() {
+    let path = env::temp_dir().join(format!("boggle-session-pause-{}.json", process::id()));
+    let (input, sender) = ChannelInput::new();
+    for line in &["1", "eric", "cats", ":pause"] {
+        sender.send(InputEvent::Line(format!("{}\n", line))).unwrap();
+    }
+    sender.send(InputEvent::Interrupt).unwrap();
+    sender.send(InputEvent::Line(String::from("q\n"))).unwrap();
+    let interrupted = input.interrupted();
+    let dictionary = Dictionary::from_words(vec!["cat", "cats", "dog"]);
+    let clock = ManualClock::new();
+    let mut session = GameSession::new(input, Console::plain(vec![]), clock, Some(dictionary));
+    session.use_board(board());
+    session.set_save_path(&path);
+    session.set_interrupted(interrupted);
+    session.run().unwrap();
+
+    let output = String::from_utf8(session.output().clone()).unwrap();
+    assert!(output.contains("Saved with 60 seconds left"), "{}", output);
+    let saved = SavedGame::load(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    assert_eq!(saved.game.guesses.len(), 1);
+}